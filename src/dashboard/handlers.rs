@@ -1,14 +1,17 @@
-use std::sync::Arc;
-
 use actix_web::{web, HttpResponse, Result};
 use askama::Template;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::dashboard::types::RequestId;
+use crate::server::error::ToolError;
 use crate::shared::{
     config::Config,
-    state::{AppState, ToolCall, ToolCallResult},
+    reload::SharedConfig,
+    state::{AppState, HistogramSummary, MetricValue, SystemEvent, ToolCall, ToolCallResult},
 };
+use crate::tools::ToolRegistry;
 
 // Standard error response structure
 #[derive(Serialize)]
@@ -117,6 +120,7 @@ struct StatusTemplate {
     started_at: String,
     last_heartbeat: String,
     has_heartbeat: bool,
+    heartbeat_stale: bool,
     active_sessions: usize,
     total_tool_calls: usize,
 }
@@ -131,6 +135,9 @@ struct ToolsTemplate {
 #[template(path = "components/tool_calls.html")]
 struct ToolCallsTemplate {
     tool_calls: Vec<FormattedToolCall>,
+    /// Calls matching the filters, across all pages
+    total: usize,
+    offset: usize,
 }
 
 #[derive(Template)]
@@ -144,6 +151,7 @@ struct ToolInfo {
     name: String,
     description: String,
     category: String,
+    examples: Vec<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -167,6 +175,14 @@ struct DashboardMetrics {
     avg_duration_ms: f64,
     tools_available: usize,
     resources_available: usize,
+    /// Summary statistics of every histogram metric, by name
+    histograms: Vec<HistogramMetric>,
+}
+
+#[derive(Serialize)]
+struct HistogramMetric {
+    name: String,
+    summary: HistogramSummary,
 }
 
 pub async fn index(dev_mode: web::Data<bool>) -> Result<HttpResponse> {
@@ -186,24 +202,87 @@ pub async fn index(dev_mode: web::Data<bool>) -> Result<HttpResponse> {
         })?))
 }
 
-pub async fn health_check() -> Result<HttpResponse> {
+/// Readiness check reporting MCP connection, static assets and heartbeat age.
+///
+/// Returns 503 when MCP is disconnected or the heartbeat is older than
+/// `health_heartbeat_max_age_seconds` (if set).
+pub async fn health_check(
+    config: web::Data<Config>,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse> {
+    let status = data.mcp_status.load();
+    let now = chrono::Utc::now();
+    let heartbeat_age = status
+        .last_heartbeat
+        .map(|hb| (now - hb).num_seconds().max(0) as u64);
+    let max_age = config.server.health_heartbeat_max_age_seconds;
+    let heartbeat_fresh = max_age == 0 || heartbeat_age.is_some_and(|age| age <= max_age);
+    let static_readable = std::fs::read_dir(super::server::STATIC_DIR).is_ok();
+
+    let healthy = status.connected && heartbeat_fresh;
+    let body = serde_json::json!({
+        "status": if healthy { "healthy" } else { "degraded" },
+        "timestamp": config.server.timestamp_format.format(now),
+        "checks": {
+            "mcp": { "healthy": status.connected },
+            "static_dir": { "healthy": static_readable },
+            "heartbeat": {
+                "healthy": heartbeat_fresh,
+                "age_seconds": heartbeat_age,
+                "max_age_seconds": (max_age > 0).then_some(max_age)
+            }
+        }
+    });
+
+    Ok(if healthy {
+        HttpResponse::Ok().json(body)
+    } else {
+        HttpResponse::ServiceUnavailable().json(body)
+    })
+}
+
+/// Liveness check that succeeds whenever the server can answer
+pub async fn health_live(config: web::Data<Config>) -> Result<HttpResponse> {
     Ok(HttpResponse::Ok().json(serde_json::json!({
-        "status": "healthy",
-        "timestamp": chrono::Utc::now()
+        "status": "alive",
+        "timestamp": config.server.timestamp_format.format(chrono::Utc::now())
     })))
 }
 
+/// OpenAPI 3.0 document describing the `/api` routes
+pub async fn get_openapi() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(crate::shared::openapi::openapi_spec()))
+}
+
+pub async fn get_version(config: web::Data<Config>) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(version_info(&config)))
+}
+
+/// Package name, version and build metadata
+fn version_info(config: &Config) -> serde_json::Value {
+    let build_timestamp = env!("BUILD_TIMESTAMP")
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0));
+    let features: Vec<&str> = env!("BUILD_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect();
+
+    serde_json::json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "git_hash": env!("BUILD_GIT_HASH"),
+        "build_timestamp": build_timestamp.map(|ts| config.server.timestamp_format.format(ts)),
+        "features": features
+    })
+}
+
 pub async fn get_status(data: web::Data<AppState>) -> Result<HttpResponse> {
-    let mut status = data.mcp_status.load().as_ref().clone();
+    let status = data.mcp_status.load();
     let active_sessions = data.active_sessions.len();
     let total_tool_calls = data.tool_calls.read().await.len();
 
-    // Update heartbeat to current time for real-time updates
-    status.last_heartbeat = Some(chrono::Utc::now());
-
-    // Update the stored status
-    data.mcp_status.store(Arc::new(status.clone()));
-
     let template = StatusTemplate {
         status_class: if status.connected {
             "connected".to_string()
@@ -227,6 +306,7 @@ pub async fn get_status(data: web::Data<AppState>) -> Result<HttpResponse> {
             .map(|hb| hb.format("%Y-%m-%d %H:%M:%S UTC").to_string())
             .unwrap_or_else(|| "Never".to_string()),
         has_heartbeat: status.last_heartbeat.is_some(),
+        heartbeat_stale: status.is_stale(chrono::Utc::now()),
         active_sessions,
         total_tool_calls,
     };
@@ -241,7 +321,50 @@ pub async fn get_status(data: web::Data<AppState>) -> Result<HttpResponse> {
         })?))
 }
 
-pub async fn get_metrics(data: web::Data<AppState>) -> Result<HttpResponse> {
+/// Machine-readable form of the `/api/status` fragment
+pub async fn get_status_json(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    let timestamp_format = config.server.timestamp_format;
+    let status = data.mcp_status.load();
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "connected": status.connected,
+        "last_heartbeat": status.last_heartbeat.map(|hb| timestamp_format.format(hb)),
+        "is_stale": status.is_stale(chrono::Utc::now()),
+        "capabilities": status.capabilities,
+        "server_info": status.server_info,
+        "started_at": timestamp_format.format(status.started_at),
+        "active_sessions": data.active_sessions.len(),
+        "total_tool_calls": data.tool_calls.read().await.len()
+    })))
+}
+
+pub async fn get_metrics(
+    data: web::Data<AppState>,
+    registry: web::Data<ToolRegistry>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    let metrics = dashboard_metrics(&data, &registry, &config).await;
+    let template = MetricsTemplate { metrics };
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html")
+        .body(template.render().map_err(|e| {
+            tracing::error!("Template rendering error: {}", e);
+            actix_web::error::ErrorInternalServerError(
+                "Internal server error: template rendering failed",
+            )
+        })?))
+}
+
+/// Summary of the tool call history shown on the dashboard
+async fn dashboard_metrics(
+    data: &AppState,
+    registry: &ToolRegistry,
+    config: &Config,
+) -> DashboardMetrics {
     let tool_calls = data.tool_calls.read().await;
     let total_calls = tool_calls.len();
 
@@ -266,33 +389,130 @@ pub async fn get_metrics(data: web::Data<AppState>) -> Result<HttpResponse> {
         100.0
     };
 
-    let metrics = DashboardMetrics {
+    let mut histograms: Vec<_> = data
+        .metrics
+        .iter()
+        .filter(|entry| matches!(entry.value(), MetricValue::Histogram(_)))
+        .map(|entry| HistogramMetric {
+            name: entry.key().clone(),
+            summary: entry.value().summary(),
+        })
+        .collect();
+    histograms.sort_by(|a, b| a.name.cmp(&b.name));
+
+    DashboardMetrics {
         total_tool_calls: total_calls,
         success_rate: (success_rate * 10.0).round() / 10.0, // Round to 1 decimal place
         active_sessions: data.active_sessions.len(),
         avg_duration_ms: avg_duration.round(),
-        tools_available: 1,     // file_search
-        resources_available: 0, // disabled
-    };
-
-    let template = MetricsTemplate { metrics };
+        tools_available: registry.tool_count(),
+        resources_available: crate::server::mcp_router::resource_count(config),
+        histograms,
+    }
+}
 
+/// Metrics in Prometheus text exposition format, for scraping
+pub async fn prometheus_metrics(data: web::Data<AppState>) -> Result<HttpResponse> {
     Ok(HttpResponse::Ok()
-        .content_type("text/html")
-        .body(template.render().map_err(|e| {
-            tracing::error!("Template rendering error: {}", e);
-            actix_web::error::ErrorInternalServerError(
-                "Internal server error: template rendering failed",
-            )
-        })?))
+        .content_type("text/plain; version=0.0.4")
+        .body(data.export_prometheus()))
+}
+
+/// Tool calls shown per page unless `limit` is given
+const DEFAULT_TOOL_CALLS_LIMIT: usize = 20;
+
+/// Largest accepted `limit` for `/api/tool-calls`
+const MAX_TOOL_CALLS_LIMIT: usize = 200;
+
+/// Outcome filter for `/api/tool-calls`
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCallStatus {
+    Success,
+    Error,
+}
+
+#[derive(Deserialize)]
+pub struct ToolCallsQuery {
+    pub caller: Option<String>,
+    pub tag: Option<String>,
+    /// Only include calls to this tool
+    pub tool: Option<String>,
+    /// Only include calls with this outcome
+    pub status: Option<ToolCallStatus>,
+    /// Number of calls to return, newest first
+    pub limit: Option<usize>,
+    /// Number of newer calls to skip
+    pub offset: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct ClearToolCallsQuery {
+    /// Also reset the per-tool call counters
+    #[serde(default)]
+    pub reset_metrics: bool,
+}
+
+/// Empties the tool call history. Only available with debug routes enabled.
+pub async fn clear_tool_calls(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+    query: web::Query<ClearToolCallsQuery>,
+) -> Result<HttpResponse> {
+    if !config.development.enable_debug_routes {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse::new(
+            "Debug routes are disabled".to_string(),
+            ERROR_TYPE_VALIDATION,
+        )));
+    }
+
+    let cleared = data.clear_tool_calls(query.reset_metrics).await;
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "cleared": cleared,
+        "metrics_reset": query.reset_metrics
+    })))
 }
 
-pub async fn get_tool_calls(data: web::Data<AppState>) -> Result<HttpResponse> {
+pub async fn get_tool_calls(
+    data: web::Data<AppState>,
+    query: web::Query<ToolCallsQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(DEFAULT_TOOL_CALLS_LIMIT);
+    if !(1..=MAX_TOOL_CALLS_LIMIT).contains(&limit) {
+        return Ok(HttpResponse::BadRequest().json(ErrorResponse::with_details(
+            format!("limit must be between 1 and {MAX_TOOL_CALLS_LIMIT}"),
+            ERROR_TYPE_VALIDATION,
+            serde_json::json!({ "limit": limit }),
+        )));
+    }
+    let offset = query.offset.unwrap_or(0);
+
     let calls = data.tool_calls.read().await;
-    let recent_calls: Vec<FormattedToolCall> = calls
+    let matching: Vec<&ToolCall> = calls
         .iter()
         .rev()
-        .take(20)
+        .filter(|call| {
+            query
+                .caller
+                .as_ref()
+                .is_none_or(|caller| call.caller.as_ref() == Some(caller))
+        })
+        .filter(|call| query.tag.as_ref().is_none_or(|tag| call.tags.contains(tag)))
+        .filter(|call| query.tool.as_ref().is_none_or(|tool| call.name == *tool))
+        .filter(|call| {
+            query.status.is_none_or(|status| match status {
+                ToolCallStatus::Success => call.success,
+                // Pending calls have neither outcome yet
+                ToolCallStatus::Error => call.result.is_some() && !call.success,
+            })
+        })
+        .collect();
+    let total = matching.len();
+    let recent_calls: Vec<FormattedToolCall> = matching
+        .into_iter()
+        .skip(offset)
+        .take(limit)
         .map(|call| FormattedToolCall {
             name: call.name.clone(),
             duration_ms: call
@@ -321,10 +541,13 @@ pub async fn get_tool_calls(data: web::Data<AppState>) -> Result<HttpResponse> {
 
     let template = ToolCallsTemplate {
         tool_calls: recent_calls,
+        total,
+        offset,
     };
 
     Ok(HttpResponse::Ok()
         .content_type("text/html")
+        .insert_header(("X-Total-Count", total))
         .body(template.render().map_err(|e| {
             tracing::error!("Template rendering error: {}", e);
             actix_web::error::ErrorInternalServerError(
@@ -333,12 +556,26 @@ pub async fn get_tool_calls(data: web::Data<AppState>) -> Result<HttpResponse> {
         })?))
 }
 
-pub async fn list_tools(_data: web::Data<AppState>) -> Result<HttpResponse> {
-    let tools = vec![ToolInfo {
-        name: "file_search".to_string(),
-        description: "Search for files and directories with advanced filtering options".to_string(),
-        category: "filesystem".to_string(),
-    }];
+pub async fn list_tools(
+    registry: web::Data<ToolRegistry>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    let show_examples = config.server.enable_tool_examples;
+    let mut tools: Vec<ToolInfo> = registry
+        .list_tools()
+        .into_iter()
+        .map(|tool| ToolInfo {
+            category: tool_category(&tool.name).to_string(),
+            examples: if show_examples {
+                tool.examples
+            } else {
+                Vec::new()
+            },
+            name: tool.name,
+            description: tool.description,
+        })
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
 
     let template = ToolsTemplate { tools };
 
@@ -352,6 +589,94 @@ pub async fn list_tools(_data: web::Data<AppState>) -> Result<HttpResponse> {
         })?))
 }
 
+/// Input and output JSON Schemas of a tool, for building tool forms
+pub async fn get_tool_schema(
+    registry: web::Data<ToolRegistry>,
+    name: web::Path<String>,
+) -> Result<HttpResponse> {
+    match registry.get_tool(&name) {
+        Some(tool) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "input_schema": tool.input_schema,
+            "output_schema": tool.output_schema,
+        }))),
+        None => Ok(HttpResponse::NotFound().json(ErrorResponse::new(
+            format!("Tool not found: {name}"),
+            ERROR_TYPE_VALIDATION,
+        ))),
+    }
+}
+
+/// Dashboard category for a tool, derived from its name
+fn tool_category(name: &str) -> &'static str {
+    if ["file", "dir", "path", "encoding"]
+        .iter()
+        .any(|word| name.contains(word))
+    {
+        "filesystem"
+    } else {
+        "general"
+    }
+}
+
+/// Per-tool last success and last error times, for diagnosing flaky tools
+pub async fn get_tool_health(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    if !config.server.enable_tool_health {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse::new(
+            "Tool health reporting is disabled".to_string(),
+            ERROR_TYPE_VALIDATION,
+        )));
+    }
+
+    let timestamp_format = config.server.timestamp_format;
+    let mut tools: Vec<_> = data
+        .tool_health
+        .iter()
+        .map(|entry| {
+            let health = entry.value();
+            serde_json::json!({
+                "name": entry.key(),
+                "last_success": health.last_success.map(|ts| timestamp_format.format(ts)),
+                "last_error": health.last_error.map(|ts| timestamp_format.format(ts)),
+                "last_error_message": health.last_error_message
+            })
+        })
+        .collect();
+    tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    Ok(HttpResponse::Ok().json(tools))
+}
+
+/// Effective limits, so clients know their constraints upfront
+pub async fn get_limits(config: web::Data<SharedConfig>) -> Result<HttpResponse> {
+    let config = config.load();
+    if !config.server.enable_limits_reporting {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse::new(
+            "Limits reporting is disabled".to_string(),
+            ERROR_TYPE_VALIDATION,
+        )));
+    }
+
+    Ok(HttpResponse::Ok().json(config.limits_report()))
+}
+
+/// Tools ranked by how often they are called, from the tool call history
+pub async fn get_tool_usage(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    if !config.server.enable_tool_usage {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse::new(
+            "Tool usage reporting is disabled".to_string(),
+            ERROR_TYPE_VALIDATION,
+        )));
+    }
+
+    Ok(HttpResponse::Ok().json(data.tool_usage().await))
+}
+
 pub async fn list_resources(_data: web::Data<AppState>) -> Result<HttpResponse> {
     // Resources are disabled in this simplified MCP server
     let resources: Vec<serde_json::Value> = vec![];
@@ -359,18 +684,47 @@ pub async fn list_resources(_data: web::Data<AppState>) -> Result<HttpResponse>
     Ok(HttpResponse::Ok().json(resources))
 }
 
-pub async fn get_events(_data: web::Data<AppState>) -> Result<HttpResponse> {
-    // Return recent events from the event log
-    let events = vec![serde_json::json!({
-        "timestamp": chrono::Utc::now(),
-        "type": "info",
-        "message": "Dashboard server started"
-    })];
+pub async fn get_events(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    let timestamp_format = config.server.timestamp_format;
+    let events: Vec<_> = data
+        .get_events(100)
+        .await
+        .into_iter()
+        .map(|recorded| {
+            let (event_type, message) = describe_event(&recorded.event);
+            serde_json::json!({
+                "timestamp": timestamp_format.format(recorded.timestamp),
+                "type": event_type,
+                "message": message
+            })
+        })
+        .collect();
 
     Ok(HttpResponse::Ok().json(events))
 }
 
-pub async fn get_sessions(data: web::Data<AppState>) -> Result<HttpResponse> {
+/// Severity and human-readable message for an event
+fn describe_event(event: &SystemEvent) -> (&'static str, String) {
+    match event {
+        SystemEvent::McpConnected => ("info", "MCP server connected".to_string()),
+        SystemEvent::McpDisconnected => ("warning", "MCP server disconnected".to_string()),
+        SystemEvent::ToolCalled { name, id } => ("info", format!("Tool {name} called ({id})")),
+        SystemEvent::ResourceAccessed { uri } => ("info", format!("Resource {uri} accessed")),
+        SystemEvent::Error { message } => ("error", message.clone()),
+        SystemEvent::SessionStarted { id } => ("info", format!("Session {id} started")),
+        SystemEvent::SessionExpired { id } => ("info", format!("Session {id} expired")),
+        SystemEvent::Custom(message) => ("info", message.clone()),
+    }
+}
+
+pub async fn get_sessions(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    let timestamp_format = config.server.timestamp_format;
     let sessions: Vec<_> = data
         .active_sessions
         .iter()
@@ -378,9 +732,9 @@ pub async fn get_sessions(data: web::Data<AppState>) -> Result<HttpResponse> {
             let session = entry.value();
             serde_json::json!({
                 "id": session.id,
-                "started_at": session.started_at,
+                "started_at": timestamp_format.format(session.started_at),
                 "request_count": session.request_count,
-                "last_activity": session.last_activity
+                "last_activity": timestamp_format.format(session.last_activity)
             })
         })
         .collect();
@@ -388,33 +742,69 @@ pub async fn get_sessions(data: web::Data<AppState>) -> Result<HttpResponse> {
     Ok(HttpResponse::Ok().json(sessions))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct ExecuteToolRequest {
     pub name: String,
     pub arguments: serde_json::Value,
+    #[serde(default)]
+    pub caller: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct ExecuteToolResponse {
     pub success: bool,
     pub result: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Machine-readable error code, e.g. `TOOL_NOT_FOUND`
+    pub error_code: Option<&'static str>,
     pub tool_call_id: String,
 }
 
+impl ExecuteToolResponse {
+    fn failure(tool_call_id: Uuid, error: String, code: &'static str) -> Self {
+        Self {
+            success: false,
+            result: None,
+            error: Some(error),
+            error_code: Some(code),
+            tool_call_id: tool_call_id.to_string(),
+        }
+    }
+}
+
 pub async fn execute_tool(
     data: web::Data<AppState>,
     payload: web::Json<ExecuteToolRequest>,
+    request_id: Option<web::ReqData<RequestId>>,
 ) -> Result<HttpResponse> {
     let tool_call_id = Uuid::new_v4();
+
+    // Wait for a free slot, or ask the client to back off
+    let _permit = match data.acquire_tool_permit(&payload.name).await {
+        Ok(permit) => permit,
+        Err(e) => {
+            return Ok(
+                HttpResponse::TooManyRequests().json(ExecuteToolResponse::failure(
+                    tool_call_id,
+                    e.to_string(),
+                    e.code(),
+                )),
+            );
+        }
+    };
+
     let start_time = std::time::Instant::now();
 
     // Create initial tool call record
-    let mut tool_call = ToolCall::new(payload.name.clone(), payload.arguments.clone());
+    let mut tool_call = ToolCall::new(payload.name.clone(), payload.arguments.clone())
+        .with_metadata(payload.caller.clone(), payload.tags.clone());
     tool_call.id = tool_call_id;
+    tool_call.request_id = request_id.map(|id| id.into_inner().0);
 
     // Execute the tool based on its name
-    let result: Result<serde_json::Value, String> = match payload.name.as_str() {
+    let result: Result<serde_json::Value, ToolError> = match payload.name.as_str() {
         "file_search" => match payload.arguments.get("query").and_then(|v| v.as_str()) {
             Some(query) => {
                 // Sanitize the input query
@@ -426,33 +816,35 @@ pub async fn execute_tool(
                     })),
                     Err(error_response) => {
                         // Return structured error response
-                        return Ok(HttpResponse::BadRequest().json(ExecuteToolResponse {
-                            success: false,
-                            result: None,
-                            error: Some(error_response.error),
-                            tool_call_id: tool_call_id.to_string(),
-                        }));
+                        let error = ToolError::InvalidInput(error_response.error);
+                        return Ok(
+                            HttpResponse::BadRequest().json(ExecuteToolResponse::failure(
+                                tool_call_id,
+                                error.to_string(),
+                                error.code(),
+                            )),
+                        );
                     }
                 }
             }
             None => {
-                let error_response = ErrorResponse::new(
-                    "Missing required parameter: query".to_string(),
-                    ERROR_TYPE_VALIDATION,
+                let error =
+                    ToolError::InvalidInput("Missing required parameter: query".to_string());
+                return Ok(
+                    HttpResponse::BadRequest().json(ExecuteToolResponse::failure(
+                        tool_call_id,
+                        error.to_string(),
+                        error.code(),
+                    )),
                 );
-                return Ok(HttpResponse::BadRequest().json(ExecuteToolResponse {
-                    success: false,
-                    result: None,
-                    error: Some(error_response.error),
-                    tool_call_id: tool_call_id.to_string(),
-                }));
             }
         },
         _ => {
-            let error_msg = format!(
-                "Unknown tool: {} (only 'file_search' tool is available)",
+            let error = ToolError::ToolNotFound(format!(
+                "{} (only 'file_search' tool is available)",
                 payload.name
-            );
+            ));
+            let error_msg = error.to_string();
             tool_call.result = Some(ToolCallResult::Error(error_msg.clone()));
             tool_call.success = false;
             tool_call.error = Some(error_msg.clone());
@@ -460,12 +852,13 @@ pub async fn execute_tool(
                 tracing::error!("Failed to record tool call: {e}");
             });
 
-            return Ok(HttpResponse::BadRequest().json(ExecuteToolResponse {
-                success: false,
-                result: None,
-                error: Some(error_msg),
-                tool_call_id: tool_call_id.to_string(),
-            }));
+            return Ok(
+                HttpResponse::BadRequest().json(ExecuteToolResponse::failure(
+                    tool_call_id,
+                    error_msg,
+                    error.code(),
+                )),
+            );
         }
     };
 
@@ -482,6 +875,7 @@ pub async fn execute_tool(
                 success: true,
                 result: Some(result_data),
                 error: None,
+                error_code: None,
                 tool_call_id: tool_call_id.to_string(),
             }
         }
@@ -490,12 +884,7 @@ pub async fn execute_tool(
             tool_call.result = Some(ToolCallResult::Error(error_msg.clone()));
             tool_call.success = false;
             tool_call.error = Some(error_msg.clone());
-            ExecuteToolResponse {
-                success: false,
-                result: None,
-                error: Some(error_msg),
-                tool_call_id: tool_call_id.to_string(),
-            }
+            ExecuteToolResponse::failure(tool_call_id, error_msg, error.code())
         }
     };
 
@@ -507,8 +896,98 @@ pub async fn execute_tool(
     Ok(HttpResponse::Ok().json(response))
 }
 
+#[derive(Deserialize)]
+pub struct DirectoryStreamQuery {
+    pub path: String,
+}
+
+/// Streams the entries of a directory as NDJSON while they are read, so
+/// large directories can be rendered incrementally.
+pub async fn stream_directory(
+    config: web::Data<SharedConfig>,
+    query: web::Query<DirectoryStreamQuery>,
+) -> Result<HttpResponse> {
+    if !config.load().security.enable_directory_streaming {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse::new(
+            "Directory streaming is disabled".to_string(),
+            ERROR_TYPE_VALIDATION,
+        )));
+    }
+
+    let path = match tokio::fs::canonicalize(&query.path).await {
+        Ok(path) => path,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(ErrorResponse::with_details(
+                format!("Cannot read directory: {e}"),
+                ERROR_TYPE_VALIDATION,
+                serde_json::json!({ "path": query.path }),
+            )));
+        }
+    };
+
+    // Only list directories under a configured root; symlinks and `..` are
+    // resolved above so they cannot escape it
+    let roots = config.load().security.directory_streaming_roots.clone();
+    let mut allowed = false;
+    for root in &roots {
+        if let Ok(root) = tokio::fs::canonicalize(root).await {
+            if path.starts_with(&root) {
+                allowed = true;
+                break;
+            }
+        }
+    }
+    if !allowed {
+        return Ok(HttpResponse::Forbidden().json(ErrorResponse::with_details(
+            "Directory is outside the allowed streaming roots".to_string(),
+            ERROR_TYPE_VALIDATION,
+            serde_json::json!({ "path": query.path }),
+        )));
+    }
+
+    let mut entries = match tokio::fs::read_dir(&path).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest().json(ErrorResponse::with_details(
+                format!("Cannot read directory: {e}"),
+                ERROR_TYPE_VALIDATION,
+                serde_json::json!({ "path": path.display().to_string() }),
+            )));
+        }
+    };
+
+    let stream = async_stream::stream! {
+        loop {
+            let line = match entries.next_entry().await {
+                Ok(Some(entry)) => {
+                    let metadata = entry.metadata().await.ok();
+                    serde_json::json!({
+                        "name": entry.file_name().to_string_lossy(),
+                        "path": entry.path().to_string_lossy(),
+                        "is_dir": metadata.as_ref().is_some_and(|m| m.is_dir()),
+                        "size": metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+                    })
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    // Report the failure in-band and end the stream
+                    let error_line = serde_json::json!({ "error": e.to_string() });
+                    yield Ok::<_, actix_web::Error>(web::Bytes::from(format!("{error_line}\n")));
+                    break;
+                }
+            };
+
+            yield Ok::<_, actix_web::Error>(web::Bytes::from(format!("{line}\n")));
+        }
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .streaming(stream))
+}
+
 pub async fn get_config(config: web::Data<Config>) -> Result<HttpResponse> {
-    Ok(HttpResponse::Ok().json(config.get_ref()))
+    Ok(HttpResponse::Ok().json(config.redacted()))
 }
 
 pub async fn debug_config(config: web::Data<Config>) -> Result<HttpResponse> {
@@ -532,24 +1011,118 @@ pub async fn debug_config(config: web::Data<Config>) -> Result<HttpResponse> {
     })))
 }
 
-pub async fn debug_state(data: web::Data<AppState>) -> Result<HttpResponse> {
+pub async fn debug_state(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(state_summary(&data, &config).await))
+}
+
+/// MCP connection status, session and call counts, and uptime
+async fn state_summary(data: &AppState, config: &Config) -> serde_json::Value {
+    let timestamp_format = config.server.timestamp_format;
     let status = data.mcp_status.load();
     let active_sessions = data.active_sessions.len();
-    let tool_calls = data.tool_calls.read().await;
-    let tool_calls_count = tool_calls.len();
+    let tool_calls_count = data.tool_calls.read().await.len();
 
-    Ok(HttpResponse::Ok().json(serde_json::json!({
+    serde_json::json!({
         "mcp_status": {
             "connected": status.connected,
             "capabilities": status.capabilities,
             "server_info": status.server_info,
-            "started_at": status.started_at,
-            "last_heartbeat": status.last_heartbeat
+            "started_at": timestamp_format.format(status.started_at),
+            "last_heartbeat": status.last_heartbeat.map(|hb| timestamp_format.format(hb))
         },
         "active_sessions": active_sessions,
         "total_tool_calls": tool_calls_count,
         "uptime_seconds": chrono::Utc::now().signed_duration_since(status.started_at).num_seconds()
-    })))
+    })
+}
+
+/// Number of recent errors and failed tool calls included in diagnostics
+const DIAGNOSTICS_RECENT_ERRORS: usize = 50;
+
+/// Everything needed to debug a deployment in one downloadable JSON file.
+///
+/// Environment variables are left out; use `/debug/config` for those.
+pub async fn get_diagnostics(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+    registry: web::Data<ToolRegistry>,
+) -> Result<HttpResponse> {
+    if !config.server.enable_diagnostics {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse::new(
+            "Diagnostics are disabled".to_string(),
+            ERROR_TYPE_VALIDATION,
+        )));
+    }
+
+    let timestamp_format = config.server.timestamp_format;
+    let now = chrono::Utc::now();
+
+    let error_events: Vec<_> = data
+        .get_events(usize::MAX)
+        .await
+        .into_iter()
+        .filter_map(|recorded| match recorded.event {
+            SystemEvent::Error { message } => Some(serde_json::json!({
+                "timestamp": timestamp_format.format(recorded.timestamp),
+                "message": message
+            })),
+            _ => None,
+        })
+        .take(DIAGNOSTICS_RECENT_ERRORS)
+        .collect();
+
+    let failed_tool_calls: Vec<_> = data
+        .tool_calls
+        .read()
+        .await
+        .iter()
+        .rev()
+        .filter_map(|call| match &call.result {
+            Some(ToolCallResult::Error(error)) => Some(serde_json::json!({
+                "id": call.id,
+                "tool_name": call.name,
+                "timestamp": timestamp_format.format(call.timestamp),
+                "error": error
+            })),
+            _ => None,
+        })
+        .take(DIAGNOSTICS_RECENT_ERRORS)
+        .collect();
+
+    let mut config_json = config.redacted();
+    if config.server.redact_limits {
+        if let Some(security) = config_json
+            .get_mut("security")
+            .and_then(|s| s.as_object_mut())
+        {
+            security.remove("allowed_file_extensions");
+        }
+    }
+
+    let bundle = serde_json::json!({
+        "generated_at": timestamp_format.format(now),
+        "version": version_info(&config),
+        "status": state_summary(&data, &config).await,
+        "metrics": dashboard_metrics(&data, &registry, &config).await,
+        "recent_errors": {
+            "events": error_events,
+            "failed_tool_calls": failed_tool_calls
+        },
+        "config": config_json
+    });
+
+    Ok(HttpResponse::Ok()
+        .insert_header((
+            "Content-Disposition",
+            format!(
+                "attachment; filename=\"mcp-diagnostics-{}.json\"",
+                now.format("%Y%m%dT%H%M%SZ")
+            ),
+        ))
+        .json(bundle))
 }
 
 pub async fn debug_events(data: web::Data<AppState>) -> Result<HttpResponse> {
@@ -559,11 +1132,16 @@ pub async fn debug_events(data: web::Data<AppState>) -> Result<HttpResponse> {
     })))
 }
 
-pub async fn get_heartbeat(data: web::Data<AppState>) -> Result<HttpResponse> {
+pub async fn get_heartbeat(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
     let status = data.mcp_status.load().as_ref().clone();
+    let timestamp_format = config.server.timestamp_format;
 
     let heartbeat_data = serde_json::json!({
-        "timestamp": status.last_heartbeat,
+        "timestamp": status.last_heartbeat.map(|hb| timestamp_format.format(hb)),
+        "is_stale": status.is_stale(chrono::Utc::now()),
         "connected": status.connected,
         "uptime_seconds": chrono::Utc::now().signed_duration_since(status.started_at).num_seconds(),
         "active_sessions": data.active_sessions.len(),
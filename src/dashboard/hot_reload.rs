@@ -1,4 +1,6 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use notify::RecursiveMode;
@@ -7,6 +9,48 @@ use tokio::sync::broadcast;
 
 use crate::shared::{config::Config, state::AppState};
 
+/// Directories watched for changes, relative to the watcher root
+const WATCHED_DIRS: [&str; 4] = ["static", "templates", "config", "src"];
+
+lazy_static::lazy_static! {
+    /// Directories currently watched by a running `HotReloadWatcher`
+    static ref ACTIVE_WATCH_DIRS: Mutex<HashSet<PathBuf>> = Mutex::new(HashSet::new());
+}
+
+/// Exclusive claim on a set of watched directories, released on drop
+struct WatchClaim {
+    dirs: Vec<PathBuf>,
+}
+
+impl WatchClaim {
+    fn acquire(dirs: Vec<PathBuf>) -> anyhow::Result<Self> {
+        let mut active = ACTIVE_WATCH_DIRS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(dir) = dirs.iter().find(|dir| active.contains(*dir)) {
+            anyhow::bail!(
+                "A hot-reload watcher is already running for {}",
+                dir.display()
+            );
+        }
+
+        active.extend(dirs.iter().cloned());
+        Ok(Self { dirs })
+    }
+}
+
+impl Drop for WatchClaim {
+    fn drop(&mut self) {
+        let mut active = ACTIVE_WATCH_DIRS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for dir in &self.dirs {
+            active.remove(dir);
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ReloadEvent {
     FrontendChanged,
@@ -17,10 +61,21 @@ pub struct HotReloadWatcher {
     state: AppState,
     reload_tx: broadcast::Sender<ReloadEvent>,
     config: Config,
+    root: PathBuf,
 }
 
 impl HotReloadWatcher {
+    /// Create a watcher for the project directories under the current directory
     pub fn new(state: AppState, config: Config) -> (Self, broadcast::Receiver<ReloadEvent>) {
+        Self::with_root(state, config, ".")
+    }
+
+    /// Create a watcher for the project directories under `root`
+    pub fn with_root(
+        state: AppState,
+        config: Config,
+        root: impl Into<PathBuf>,
+    ) -> (Self, broadcast::Receiver<ReloadEvent>) {
         let (reload_tx, reload_rx) = broadcast::channel(100);
 
         (
@@ -28,15 +83,35 @@ impl HotReloadWatcher {
                 state,
                 reload_tx,
                 config,
+                root: root.into(),
             },
             reload_rx,
         )
     }
 
+    /// Absolute paths of the directories this watcher covers
+    fn watched_dirs(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let root = if self.root.is_absolute() {
+            self.root.clone()
+        } else {
+            std::env::current_dir()?.join(&self.root)
+        };
+        let root = root.canonicalize().unwrap_or(root);
+
+        Ok(WATCHED_DIRS.iter().map(|dir| root.join(dir)).collect())
+    }
+
+    /// Watch for changes until `shutdown_rx` fires.
+    ///
+    /// Only one watcher may run per directory; starting a second watcher over
+    /// the same paths fails until the first has shut down.
     pub async fn start(
         &self,
         mut shutdown_rx: tokio::sync::oneshot::Receiver<()>,
     ) -> anyhow::Result<()> {
+        let watched_dirs = self.watched_dirs()?;
+        let _claim = WatchClaim::acquire(watched_dirs.clone())?;
+
         let reload_tx = self.reload_tx.clone();
         let _state = self.state.clone();
         let debounce_ms = self.config.development.hot_reload_debounce_ms;
@@ -80,19 +155,14 @@ impl HotReloadWatcher {
                     }
                 }
             },
-        )
-        .expect("Failed to create file watcher");
+        )?;
 
-        // Watch directories
+        // Watch frontend directories, plus Rust source (informational only - won't auto-reload).
+        // Missing directories are skipped.
         let watcher = debouncer.watcher();
-
-        // Watch frontend directories
-        let _ = watcher.watch(Path::new("./static"), RecursiveMode::Recursive);
-        let _ = watcher.watch(Path::new("./templates"), RecursiveMode::Recursive);
-        let _ = watcher.watch(Path::new("./config"), RecursiveMode::Recursive);
-
-        // Watch Rust source (for informational purposes - won't auto-reload)
-        let _ = watcher.watch(Path::new("./src"), RecursiveMode::Recursive);
+        for dir in &watched_dirs {
+            let _ = watcher.watch(dir, RecursiveMode::Recursive);
+        }
 
         tracing::info!("📁 File watcher started for hot-reload");
 
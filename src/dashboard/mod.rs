@@ -5,6 +5,7 @@
 
 pub mod handlers;
 pub mod hot_reload;
+pub mod rate_limit;
 pub mod server;
 pub mod types;
 pub mod websocket;
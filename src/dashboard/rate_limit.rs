@@ -0,0 +1,140 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use dashmap::DashMap;
+
+use crate::shared::{config::RateLimitingConfig, reload::SharedConfig};
+
+/// Paths that are never rate limited so health checks cannot trip the limiter
+const EXEMPT_PATHS: [&str; 2] = ["/health", "/health/live"];
+
+/// Number of tracked clients above which idle buckets are pruned
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Token bucket for a single client
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Per client IP token bucket rate limiter for the dashboard HTTP routes
+#[derive(Clone, Default)]
+pub struct HttpRateLimiter {
+    buckets: Arc<DashMap<IpAddr, Bucket>>,
+}
+
+impl HttpRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a token for `ip`, or return how long until one is available.
+    ///
+    /// Buckets hold up to `burst_size` tokens and refill at
+    /// `requests_per_minute`.
+    pub fn check(&self, ip: IpAddr, limits: &RateLimitingConfig) -> Result<(), Duration> {
+        let capacity = f64::from(limits.burst_size.max(1));
+        let per_second = f64::from(limits.requests_per_minute.max(1)) / 60.0;
+        let now = Instant::now();
+
+        if self.buckets.len() > PRUNE_THRESHOLD {
+            // A bucket that has been idle long enough to refill is equivalent to none
+            let full_after = Duration::from_secs_f64(capacity / per_second);
+            self.buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated) < full_after);
+        }
+
+        let mut bucket = self.buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Middleware rejecting requests over the configured rate with 429
+///
+/// Limits are read from the live config so reloads take effect immediately.
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let limiter = req.app_data::<web::Data<HttpRateLimiter>>().cloned();
+    let config = req
+        .app_data::<web::Data<SharedConfig>>()
+        .map(|config| config.load_full());
+
+    if let (Some(limiter), Some(config), Some(peer)) = (limiter, config, req.peer_addr()) {
+        if config.rate_limiting.enabled && !EXEMPT_PATHS.contains(&req.path()) {
+            if let Err(retry_after) = limiter.check(peer.ip(), &config.rate_limiting) {
+                // Round up so clients never retry before a token is available
+                let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                let response = HttpResponse::TooManyRequests()
+                    .insert_header((header::RETRY_AFTER, seconds.max(1)))
+                    .json(serde_json::json!({
+                        "success": false,
+                        "error": "Too many requests",
+                        "error_type": "rate_limit",
+                        "timestamp": chrono::Utc::now().to_rfc3339(),
+                    }));
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+        }
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_left_body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(requests_per_minute: u32, burst_size: u32) -> RateLimitingConfig {
+        RateLimitingConfig {
+            enabled: true,
+            requests_per_minute,
+            burst_size,
+            ..crate::shared::config::Config::default().rate_limiting
+        }
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_rejects() {
+        let limiter = HttpRateLimiter::new();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let limits = limits(60, 3);
+
+        for _ in 0..3 {
+            assert!(limiter.check(ip, &limits).is_ok());
+        }
+        let retry_after = limiter.check(ip, &limits).unwrap_err();
+        assert!(retry_after <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_buckets_are_per_ip() {
+        let limiter = HttpRateLimiter::new();
+        let limits = limits(60, 1);
+
+        assert!(limiter.check("10.0.0.1".parse().unwrap(), &limits).is_ok());
+        assert!(limiter.check("10.0.0.1".parse().unwrap(), &limits).is_err());
+        assert!(limiter.check("10.0.0.2".parse().unwrap(), &limits).is_ok());
+    }
+}
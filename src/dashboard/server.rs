@@ -1,10 +1,23 @@
+use actix_cors::Cors;
 use actix_files::Files;
-use actix_web::{middleware, web, App, HttpServer};
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{middleware, web, App, Error, HttpMessage, HttpServer};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::Instrument;
 
 use crate::dashboard::handlers;
 use crate::dashboard::hot_reload::{HotReloadWatcher, ReloadEvent};
+use crate::dashboard::rate_limit::{self, HttpRateLimiter};
+use crate::dashboard::types::RequestId;
 use crate::dashboard::websocket;
-use crate::shared::{config::Config, state::AppState};
+use crate::shared::{config::Config, reload, state::AppState};
+use crate::tools::ToolRegistry;
 
 // Security middleware for CSP headers
 fn add_security_headers() -> middleware::DefaultHeaders {
@@ -31,6 +44,113 @@ fn add_security_headers() -> middleware::DefaultHeaders {
         .add(("Referrer-Policy", "strict-origin-when-cross-origin"))
 }
 
+/// Header carrying the id that correlates a request with its trace span
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Directory the dashboard's static assets are served from
+pub(crate) const STATIC_DIR: &str = "./static";
+
+/// Middleware tracing each request under the caller's request id, or a new one.
+///
+/// The id is stored in the request extensions as [`RequestId`] and echoed in
+/// the `X-Request-Id` response header.
+pub async fn request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+    let span = tracing::info_span!(
+        "http_request",
+        http.method = %req.method(),
+        http.target = %req.path(),
+        http.status_code = tracing::field::Empty,
+        request_id = %request_id,
+    );
+
+    let mut response = next.call(req).instrument(span.clone()).await?;
+    span.record("http.status_code", response.status().as_u16());
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+    Ok(response)
+}
+
+/// CORS policy for the dashboard.
+///
+/// With `enable_cors` any origin is allowed; otherwise only the origins in
+/// `security.websocket_allowed_origins` get CORS headers.
+pub fn cors(config: &Config) -> Cors {
+    if config.development.enable_cors {
+        return Cors::permissive();
+    }
+
+    let mut cors = Cors::default()
+        .allowed_methods(vec!["GET", "POST", "DELETE"])
+        .allow_any_header()
+        .max_age(3600);
+    for origin in &config.security.websocket_allowed_origins {
+        cors = match origin.as_str() {
+            "" => cors,
+            "*" => cors.allow_any_origin(),
+            origin => cors.allowed_origin(origin),
+        };
+    }
+    cors
+}
+
+/// Mount the `/debug` scope, which exposes the environment and internal state,
+/// only when `enabled`; otherwise those paths fall through to 404.
+pub fn debug_routes(cfg: &mut web::ServiceConfig, enabled: bool) {
+    if enabled {
+        cfg.service(
+            web::scope("/debug")
+                .route("/config", web::get().to(handlers::debug_config))
+                .route("/state", web::get().to(handlers::debug_state))
+                .route("/events", web::get().to(handlers::debug_events)),
+        );
+    }
+}
+
+/// Remove a socket file left behind by a previous run so `bind_uds` can
+/// reuse the path. Regular files are left alone.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// Build a rustls server config from a PEM certificate chain and private key
+pub fn load_tls_config(cert_path: &str, key_path: &str) -> std::io::Result<rustls::ServerConfig> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())
+    };
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| invalid(&format!("Failed to read TLS certificate {cert_path}: {e}")))?;
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|e| invalid(&format!("Failed to read TLS private key {key_path}: {e}")))?;
+
+    rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| invalid(&e))?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| invalid(&format!("Invalid TLS certificate or key: {e}")))
+}
+
 // Allow dead_code: Public API convenience function for external consumers
 // Provides simplified interface using default configuration
 #[allow(dead_code)]
@@ -47,7 +167,16 @@ pub async fn run_dashboard_with_config(
         "{}:{}",
         config.server.dashboard_host, config.server.dashboard_port
     );
-    tracing::info!("Starting dashboard server on http://{}", bind_address);
+    let socket_path = config.dashboard_socket_path().map(Path::to_path_buf);
+    let tls_config = match (&config.server.tls_cert_path, &config.server.tls_key_path) {
+        (Some(cert), Some(key)) => Some(load_tls_config(cert, key)?),
+        _ => None,
+    };
+    match (&socket_path, &tls_config) {
+        (Some(path), _) => tracing::info!("Starting dashboard server on {}", path.display()),
+        (None, Some(_)) => tracing::info!("Starting dashboard server on https://{}", bind_address),
+        (None, None) => tracing::info!("Starting dashboard server on http://{}", bind_address),
+    }
     if dev_mode {
         tracing::info!("🔥 Hot-reload enabled - file changes will trigger automatic refresh");
 
@@ -71,12 +200,9 @@ pub async fn run_dashboard_with_config(
                         // Broadcast reload message to all connected WebSocket clients
                         let reload_msg =
                             crate::dashboard::hot_reload::BrowserReloadMessage::reload();
-                        let _ =
-                            state_clone
-                                .event_tx
-                                .send(crate::shared::state::SystemEvent::Custom(
-                                    serde_json::to_string(&reload_msg).unwrap_or_default(),
-                                ));
+                        state_clone.broadcast(crate::shared::state::SystemEvent::Custom(
+                            serde_json::to_string(&reload_msg).unwrap_or_default(),
+                        ));
                     }
                     ReloadEvent::BackendChanged => {
                         tracing::info!("⚠️  Backend files changed - manual restart required (run with cargo-watch for auto-restart)");
@@ -96,51 +222,115 @@ pub async fn run_dashboard_with_config(
         });
     }
 
-    let _enable_cors = config.development.enable_cors;
-    let _enable_debug_routes = config.development.enable_debug_routes;
+    let tool_registry = ToolRegistry::with_default_tools(&config);
+    let live_config = reload::shared(config.clone());
+    let rate_limiter = HttpRateLimiter::new();
+    let ws_rate_limiter = websocket::WebSocketRateLimiter::from_config(&config.rate_limiting);
+    let ws_cleanup_limiter = ws_rate_limiter.clone();
+    let ws_cleanup_interval = std::time::Duration::from_secs(
+        config
+            .rate_limiting
+            .websocket_cleanup_interval_seconds
+            .max(1),
+    );
+    if config.server.enable_config_reload {
+        #[cfg(unix)]
+        {
+            let live_config = live_config.clone();
+            tokio::spawn(async move {
+                if let Err(e) = reload::reload_on_sighup(live_config).await {
+                    tracing::error!("Failed to listen for SIGHUP: {}", e);
+                }
+            });
+        }
+        #[cfg(not(unix))]
+        tracing::warn!("ENABLE_CONFIG_RELOAD is only supported on unix");
+    }
+    let enable_debug_routes = config.development.enable_debug_routes;
     let app = HttpServer::new(move || {
         let app_data_dev_mode = dev_mode;
         let app_builder = App::new()
             .app_data(web::Data::new(state.clone()))
             .app_data(web::Data::new(config.clone()))
+            .app_data(web::Data::new(tool_registry.clone()))
+            .app_data(web::Data::new(live_config.clone()))
             .app_data(web::Data::new(app_data_dev_mode))
+            .app_data(web::Data::new(rate_limiter.clone()))
+            .app_data(web::Data::new(ws_rate_limiter.clone()))
+            .wrap(middleware::from_fn(rate_limit::rate_limit))
             .wrap(middleware::Logger::default())
             .wrap(middleware::NormalizePath::trim())
-            .wrap(add_security_headers());
+            .wrap(add_security_headers())
+            .wrap(cors(&config))
+            .wrap(middleware::from_fn(request_id));
 
-        // For now, skip conditional CORS middleware due to type complexity
-        // TODO: Add proper CORS handling with actix-cors crate
         app_builder
             // Dashboard routes
             .route("/", web::get().to(handlers::index))
             .route("/health", web::get().to(handlers::health_check))
+            .route("/health/live", web::get().to(handlers::health_live))
+            .route("/metrics", web::get().to(handlers::prometheus_metrics))
             // API routes
             .service(
                 web::scope("/api")
                     .route("/status", web::get().to(handlers::get_status))
+                    .route("/status.json", web::get().to(handlers::get_status_json))
                     .route("/heartbeat", web::get().to(handlers::get_heartbeat))
+                    .route("/version", web::get().to(handlers::get_version))
+                    .route("/limits", web::get().to(handlers::get_limits))
                     .route("/metrics", web::get().to(handlers::get_metrics))
                     .route("/tools", web::get().to(handlers::list_tools))
                     .route("/tools/execute", web::post().to(handlers::execute_tool))
+                    .route("/tools/health", web::get().to(handlers::get_tool_health))
+                    .route("/tools/usage", web::get().to(handlers::get_tool_usage))
+                    .route(
+                        "/tools/{name}/schema",
+                        web::get().to(handlers::get_tool_schema),
+                    )
                     .route("/tool-calls", web::get().to(handlers::get_tool_calls))
+                    .route("/tool-calls", web::delete().to(handlers::clear_tool_calls))
+                    .route(
+                        "/directory/stream",
+                        web::get().to(handlers::stream_directory),
+                    )
                     .route("/resources", web::get().to(handlers::list_resources))
                     .route("/events", web::get().to(handlers::get_events))
                     .route("/sessions", web::get().to(handlers::get_sessions))
-                    .route("/config", web::get().to(handlers::get_config)),
-            )
-            // Debug routes (if enabled)
-            .service(
-                web::scope("/debug")
-                    .route("/config", web::get().to(handlers::debug_config))
-                    .route("/state", web::get().to(handlers::debug_state))
-                    .route("/events", web::get().to(handlers::debug_events)),
+                    .route("/config", web::get().to(handlers::get_config))
+                    .route("/diagnostics", web::get().to(handlers::get_diagnostics))
+                    .route("/openapi.json", web::get().to(handlers::get_openapi)),
             )
+            .configure(|cfg| debug_routes(cfg, enable_debug_routes))
             // Real-time endpoints
             .route("/ws", web::get().to(websocket::websocket_handler))
             .route("/sse", web::get().to(websocket::sse_handler))
             // Static files
-            .service(Files::new("/static", "./static").show_files_listing())
+            .service(Files::new("/static", STATIC_DIR).show_files_listing())
     });
 
-    app.bind(&bind_address)?.run().await
+    let server = match socket_path {
+        #[cfg(unix)]
+        Some(path) => {
+            remove_stale_socket(&path)?;
+            app.bind_uds(&path)?
+        }
+        #[cfg(not(unix))]
+        Some(path) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("Unix sockets are not supported here: {}", path.display()),
+            ))
+        }
+        None => match tls_config {
+            Some(tls_config) => app.bind_rustls_0_23(&bind_address, tls_config)?,
+            None => app.bind(&bind_address)?,
+        },
+    }
+    .run();
+
+    // Purge stale WebSocket rate limit entries for as long as the server runs
+    let ws_cleanup = ws_cleanup_limiter.spawn_cleanup(ws_cleanup_interval);
+    let result = server.await;
+    ws_cleanup.abort();
+    result
 }
@@ -1,2 +1,7 @@
 // Dashboard-specific types
 // This module contains types specifically for the dashboard API
+
+/// Id of the current HTTP request, stored in the request extensions by the
+/// `request_id` middleware
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
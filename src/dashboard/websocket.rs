@@ -1,14 +1,17 @@
 use actix_web::{web, HttpRequest, HttpResponse, Result};
 use actix_ws::Message;
-use futures_util::StreamExt;
-use std::collections::HashMap;
+use futures_util::{Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
 use std::net::IpAddr;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::sync::RwLock;
 
 use crate::shared::{
-    config::Config,
+    config::{Config, RateLimitingConfig},
+    reload::SharedConfig,
     state::{AppState, SystemEvent},
 };
 
@@ -22,19 +25,26 @@ pub struct WebSocketRateLimiter {
 
 impl Default for WebSocketRateLimiter {
     fn default() -> Self {
-        Self::new()
+        Self::from_config(&Config::default().rate_limiting)
     }
 }
 
 impl WebSocketRateLimiter {
-    pub fn new() -> Self {
+    pub fn new(max_connections_per_ip: usize, time_window: Duration) -> Self {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
-            max_connections_per_ip: 10, // Max 10 connections per IP
-            time_window: Duration::from_secs(60), // Per minute
+            max_connections_per_ip,
+            time_window,
         }
     }
 
+    pub fn from_config(config: &RateLimitingConfig) -> Self {
+        Self::new(
+            config.websocket_connections_per_ip,
+            Duration::from_secs(config.websocket_window_seconds),
+        )
+    }
+
     /// Check if IP is allowed to connect
     pub async fn check_rate_limit(&self, ip: IpAddr) -> bool {
         let now = Instant::now();
@@ -53,10 +63,29 @@ impl WebSocketRateLimiter {
         entry.push(now);
         true
     }
-}
 
-lazy_static::lazy_static! {
-    static ref RATE_LIMITER: WebSocketRateLimiter = WebSocketRateLimiter::new();
+    /// Drop connection times outside the window and forget IPs with none left
+    pub async fn cleanup(&self) {
+        let now = Instant::now();
+        let mut connections = self.connections.write().await;
+        connections.retain(|_, timestamps| {
+            timestamps.retain(|&timestamp| now.duration_since(timestamp) < self.time_window);
+            !timestamps.is_empty()
+        });
+    }
+
+    /// Run `cleanup` every `interval` until the returned task is aborted
+    pub fn spawn_cleanup(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let limiter = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                limiter.cleanup().await;
+            }
+        })
+    }
 }
 
 /// Validate WebSocket origin header
@@ -86,8 +115,11 @@ pub async fn websocket_handler(
     req: HttpRequest,
     stream: web::Payload,
     data: web::Data<AppState>,
-    config: web::Data<Config>,
+    config: web::Data<SharedConfig>,
+    rate_limiter: web::Data<WebSocketRateLimiter>,
 ) -> Result<HttpResponse> {
+    let config = config.load_full();
+
     // Extract client IP address
     let client_ip = req
         .connection_info()
@@ -97,7 +129,7 @@ pub async fn websocket_handler(
         .unwrap_or_else(|| std::net::IpAddr::V4(std::net::Ipv4Addr::new(127, 0, 0, 1)));
 
     // Check rate limit
-    if !RATE_LIMITER.check_rate_limit(client_ip).await {
+    if !rate_limiter.check_rate_limit(client_ip).await {
         tracing::warn!(
             "WebSocket connection rejected due to rate limit: IP {}",
             client_ip
@@ -120,7 +152,13 @@ pub async fn websocket_handler(
     let (res, mut session, mut msg_stream) = actix_ws::handle(&req, stream)?;
 
     let state = data.get_ref().clone();
-    let mut event_rx = state.event_tx.subscribe();
+    let mut events = debounced_events(
+        state.event_tx.subscribe(),
+        Duration::from_millis(config.server.event_debounce_ms),
+    );
+    let timestamp_format = config.server.timestamp_format;
+    // Event types this connection subscribed to; empty means all
+    let mut types: HashSet<&'static str> = HashSet::new();
 
     actix_web::rt::spawn(async move {
         loop {
@@ -128,62 +166,22 @@ pub async fn websocket_handler(
                 Some(msg) = msg_stream.next() => {
                     match msg {
                         Ok(Message::Text(text)) => {
-                            // Handle client messages
                             tracing::debug!("Received WS message: {}", text);
+                            let reply = handle_ws_command(&text, &mut types);
+                            if session.text(reply.to_string()).await.is_err() {
+                                break;
+                            }
                         }
                         Ok(Message::Close(_)) => break,
                         _ => {}
                     }
                 }
-                Ok(event) = event_rx.recv() => {
-                    let event_json = match event {
-                        SystemEvent::McpConnected => {
-                            serde_json::json!({
-                                "type": "mcp_connected",
-                                "timestamp": chrono::Utc::now()
-                            })
-                        }
-                        SystemEvent::McpDisconnected => {
-                            serde_json::json!({
-                                "type": "mcp_disconnected",
-                                "timestamp": chrono::Utc::now()
-                            })
-                        }
-                        SystemEvent::ToolCalled { name, id } => {
-                            serde_json::json!({
-                                "type": "tool_called",
-                                "name": name,
-                                "id": id,
-                                "timestamp": chrono::Utc::now()
-                            })
-                        }
-                        SystemEvent::ResourceAccessed { uri } => {
-                            serde_json::json!({
-                                "type": "resource_accessed",
-                                "uri": uri,
-                                "timestamp": chrono::Utc::now()
-                            })
-                        }
-                        SystemEvent::Error { message } => {
-                            serde_json::json!({
-                                "type": "error",
-                                "message": message,
-                                "timestamp": chrono::Utc::now()
-                            })
-                        }
-                        SystemEvent::Custom(payload) => {
-                            // Parse the custom payload if it's JSON, otherwise wrap it
-                            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&payload) {
-                                json
-                            } else {
-                                serde_json::json!({
-                                    "type": "custom",
-                                    "payload": payload,
-                                    "timestamp": chrono::Utc::now()
-                                })
-                            }
-                        }
-                    };
+                Some((event, count)) = events.next() => {
+                    if !types.is_empty() && !types.contains(event.event_type()) {
+                        continue;
+                    }
+
+                    let event_json = event.to_coalesced_ws_json(timestamp_format, count);
 
                     if session.text(event_json.to_string()).await.is_err() {
                         break;
@@ -196,104 +194,95 @@ pub async fn websocket_handler(
     Ok(res)
 }
 
-pub async fn sse_handler(data: web::Data<AppState>) -> Result<HttpResponse> {
-    let mut event_rx = data.event_tx.subscribe();
+/// Commands a WebSocket client can send to control its connection
+#[derive(serde::Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum WsCommand {
+    Ping,
+    Subscribe { types: Vec<String> },
+}
+
+/// Apply a client command to the connection and build the reply frame.
+///
+/// Failures reply with a `command_error` frame so they can't be mistaken for
+/// an `error` system event, and the connection stays open.
+fn handle_ws_command(text: &str, types: &mut HashSet<&'static str>) -> serde_json::Value {
+    let command = serde_json::from_str::<serde_json::Value>(text)
+        .map_err(|e| format!("Malformed JSON: {}", e))
+        .and_then(|value| {
+            serde_json::from_value::<WsCommand>(value)
+                .map_err(|e| format!("Unknown or invalid command: {}", e))
+        });
+
+    match command {
+        Ok(WsCommand::Ping) => serde_json::json!({ "type": "pong" }),
+        Ok(WsCommand::Subscribe { types: requested }) => {
+            *types = requested
+                .iter()
+                .filter_map(|name| known_event_type(name))
+                .collect();
+            let mut subscribed: Vec<_> = types.iter().copied().collect();
+            subscribed.sort_unstable();
+            serde_json::json!({ "type": "subscribed", "types": subscribed })
+        }
+        Err(message) => serde_json::json!({ "type": "command_error", "message": message }),
+    }
+}
+
+/// Event type names accepted by the SSE `types` filter and ws `subscribe`
+const SSE_EVENT_TYPES: [&str; 8] = [
+    "mcp_connected",
+    "mcp_disconnected",
+    "tool_called",
+    "resource_accessed",
+    "error",
+    "session_started",
+    "session_expired",
+    "custom",
+];
+
+#[derive(serde::Deserialize)]
+pub struct SseQuery {
+    /// Comma-separated event types to stream, e.g. `tool_called,error`
+    pub types: Option<String>,
+}
+
+/// Parse the `types` filter; unknown names are ignored and an empty
+/// result means every event is streamed
+fn parse_sse_types(types: Option<&str>) -> HashSet<&'static str> {
+    types
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(known_event_type)
+        .collect()
+}
+
+fn known_event_type(name: &str) -> Option<&'static str> {
+    SSE_EVENT_TYPES
+        .iter()
+        .copied()
+        .find(|known| *known == name.trim())
+}
+
+pub async fn sse_handler(
+    data: web::Data<AppState>,
+    config: web::Data<Config>,
+    query: web::Query<SseQuery>,
+) -> Result<HttpResponse> {
+    let mut events = debounced_events(
+        data.event_tx.subscribe(),
+        Duration::from_millis(config.server.event_debounce_ms),
+    );
+    let timestamp_format = config.server.timestamp_format;
+    let types = parse_sse_types(query.types.as_deref());
 
     let stream = async_stream::stream! {
-        while let Ok(event) = event_rx.recv().await {
-            let event_data = match event {
-                SystemEvent::McpConnected => {
-                    format!("event: mcp_connected\ndata: {}\n\n", serde_json::json!({
-                        "type": "connected",
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "html": format!(
-                            r#"<div class="alert alert-success" hx-swap-oob="afterbegin:#events-container">
-                                <span class="timestamp">{}</span>
-                                <span class="message">MCP Server Connected</span>
-                            </div>"#,
-                            chrono::Utc::now().format("%H:%M:%S")
-                        )
-                    }))
-                }
-                SystemEvent::McpDisconnected => {
-                    format!("event: mcp_disconnected\ndata: {}\n\n", serde_json::json!({
-                        "type": "disconnected",
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "html": format!(
-                            r#"<div class="alert alert-warning" hx-swap-oob="afterbegin:#events-container">
-                                <span class="timestamp">{}</span>
-                                <span class="message">MCP Server Disconnected</span>
-                            </div>"#,
-                            chrono::Utc::now().format("%H:%M:%S")
-                        )
-                    }))
-                }
-                SystemEvent::ToolCalled { name, id } => {
-                    format!("event: tool_called\ndata: {}\n\n", serde_json::json!({
-                        "type": "tool_called",
-                        "name": name,
-                        "id": id,
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "html": format!(
-                            r#"<div class="tool-call-event" hx-swap-oob="afterbegin:#tool-calls-live">
-                                <div class="tool-call">
-                                    <span class="timestamp">{}</span>
-                                    <span class="tool-name">{}</span>
-                                    <span class="tool-id">#{}</span>
-                                    <span class="status executing">Executing...</span>
-                                </div>
-                            </div>"#,
-                            chrono::Utc::now().format("%H:%M:%S"),
-                            name,
-                            id
-                        )
-                    }))
-                }
-                SystemEvent::ResourceAccessed { uri } => {
-                    format!("event: resource_accessed\ndata: {}\n\n", serde_json::json!({
-                        "type": "resource_accessed",
-                        "uri": uri,
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "html": format!(
-                            r#"<div class="resource-event" hx-swap-oob="afterbegin:#resources-live">
-                                <span class="timestamp">{}</span>
-                                <span class="resource-uri">{}</span>
-                                <span class="status accessed">Accessed</span>
-                            </div>"#,
-                            chrono::Utc::now().format("%H:%M:%S"),
-                            uri
-                        )
-                    }))
-                }
-                SystemEvent::Error { message } => {
-                    format!("event: error\ndata: {}\n\n", serde_json::json!({
-                        "type": "error",
-                        "message": message,
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "html": format!(
-                            r#"<div class="alert alert-error" hx-swap-oob="afterbegin:#events-container">
-                                <span class="timestamp">{}</span>
-                                <span class="message">Error: {}</span>
-                            </div>"#,
-                            chrono::Utc::now().format("%H:%M:%S"),
-                            message
-                        )
-                    }))
-                }
-                SystemEvent::Custom(payload) => {
-                    // For SSE, emit custom events as-is if they're JSON
-                    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&payload) {
-                        format!("event: custom\ndata: {json}\n\n")
-                    } else {
-                        let json_data = serde_json::json!({
-                            "type": "custom",
-                            "payload": payload,
-                            "timestamp": chrono::Utc::now().to_rfc3339()
-                        });
-                        format!("event: custom\ndata: {json_data}\n\n")
-                    }
-                }
-            };
+        while let Some((event, count)) = events.next().await {
+            if !types.is_empty() && !types.contains(event.event_type()) {
+                continue;
+            }
+
+            let event_data = event.to_coalesced_sse_frame(timestamp_format, count);
 
             yield Ok::<_, actix_web::Error>(web::Bytes::from(event_data));
         }
@@ -305,3 +294,140 @@ pub async fn sse_handler(data: web::Data<AppState>) -> Result<HttpResponse> {
         .insert_header(("Connection", "keep-alive"))
         .streaming(stream))
 }
+
+/// Events from `rx` with the number of broadcasts each one stands for.
+///
+/// With a non-zero `window`, events are buffered for the window after the
+/// first one arrives and then flushed as the latest event of each type, in
+/// the order the types first appeared, so a burst becomes one frame per type.
+/// Events missed because the receiver lagged are skipped.
+fn debounced_events(
+    mut rx: broadcast::Receiver<SystemEvent>,
+    window: Duration,
+) -> Pin<Box<dyn Stream<Item = (SystemEvent, u32)> + Send>> {
+    Box::pin(async_stream::stream! {
+        loop {
+            let first = match rx.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+            if window.is_zero() {
+                yield (first, 1);
+                continue;
+            }
+
+            let mut batch: Vec<(SystemEvent, u32)> = vec![(first, 1)];
+            let mut closed = false;
+            let deadline = tokio::time::sleep(window);
+            tokio::pin!(deadline);
+            loop {
+                tokio::select! {
+                    _ = &mut deadline => break,
+                    received = rx.recv() => match received {
+                        Ok(event) => {
+                            match batch
+                                .iter_mut()
+                                .find(|(pending, _)| pending.event_type() == event.event_type())
+                            {
+                                Some((pending, count)) => {
+                                    *pending = event;
+                                    *count += 1;
+                                }
+                                None => batch.push((event, 1)),
+                            }
+                        }
+                        Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => {
+                            closed = true;
+                            break;
+                        }
+                    },
+                }
+            }
+
+            for item in batch {
+                yield item;
+            }
+            if closed {
+                break;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_debounce_bounds_frames_per_window() {
+        let state = AppState::new();
+        let mut events = debounced_events(state.event_tx.subscribe(), Duration::from_millis(100));
+
+        for i in 0..100 {
+            state.broadcast(SystemEvent::ToolCalled {
+                name: format!("tool_{i}"),
+                id: uuid::Uuid::new_v4(),
+            });
+        }
+        state.broadcast(SystemEvent::Error {
+            message: "boom".to_string(),
+        });
+
+        let (event, count) = events.next().await.unwrap();
+        assert_eq!(count, 100);
+        assert!(matches!(event, SystemEvent::ToolCalled { name, .. } if name == "tool_99"));
+        let (event, count) = events.next().await.unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(event.event_type(), "error");
+
+        // Nothing else was sent, so no more frames arrive
+        let next = tokio::time::timeout(Duration::from_millis(250), events.next()).await;
+        assert!(next.is_err());
+
+        let frame = event_frame(100);
+        assert_eq!(frame["coalesced"], 100);
+        assert!(event_frame(1).get("coalesced").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_debounce_disabled_passes_events_through() {
+        let state = AppState::new();
+        let mut events = debounced_events(state.event_tx.subscribe(), Duration::ZERO);
+
+        for _ in 0..3 {
+            state.broadcast(SystemEvent::McpConnected);
+        }
+        for _ in 0..3 {
+            assert_eq!(events.next().await, Some((SystemEvent::McpConnected, 1)));
+        }
+    }
+
+    fn event_frame(count: u32) -> serde_json::Value {
+        SystemEvent::McpConnected
+            .to_coalesced_ws_json(crate::shared::config::TimestampFormat::Rfc3339, count)
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_rejects_connections_over_limit() {
+        let limiter = WebSocketRateLimiter::new(2, Duration::from_secs(60));
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        assert!(limiter.check_rate_limit(ip).await);
+        assert!(limiter.check_rate_limit(ip).await);
+        assert!(!limiter.check_rate_limit(ip).await);
+        assert!(limiter.check_rate_limit("10.0.0.2".parse().unwrap()).await);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_forgets_expired_ips() {
+        let limiter = WebSocketRateLimiter::new(2, Duration::from_millis(20));
+        assert!(limiter.check_rate_limit("10.0.0.1".parse().unwrap()).await);
+        assert_eq!(limiter.connections.read().await.len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        limiter.cleanup().await;
+        assert!(limiter.connections.read().await.is_empty());
+    }
+}
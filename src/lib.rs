@@ -36,7 +36,8 @@
 //!
 //! ## Performance
 //!
-//! - Tool call history is bounded to 1000 entries to prevent memory growth
+//! - Tool call history is a ring buffer (1000 entries by default, `MAX_TOOL_CALL_HISTORY`)
+//!   to prevent memory growth
 //! - Event broadcasting uses a 1000-message buffer
 //! - Concurrent data structures minimize lock contention
 //! - WebSocket connections are pooled for efficiency
@@ -3,12 +3,15 @@ mod server;
 mod shared;
 mod tools;
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
 use clap::Parser;
 use shared::{
-    config::Config,
+    config::{Config, RestartPolicy},
     state::{AppState, McpStatus, ServerInfo},
+    supervisor::{supervise, track_mcp_connection, Backoff, RESTART_BACKOFF},
 };
 
 #[derive(Parser)]
@@ -22,6 +25,10 @@ struct Cli {
     /// Operation mode
     #[arg(long, value_enum, default_value = "both")]
     mode: Mode,
+
+    /// TOML configuration file; environment variables override its values
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -42,32 +49,112 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Load and validate configuration
-    let config = Config::from_env().map_err(|e| format!("Configuration error: {e}"))?;
+    let cli = Cli::parse();
+
+    // Load and validate configuration, layering the environment over the file
+    let config = match &cli.config {
+        Some(path) => Config::from_file(path).and_then(Config::with_env),
+        None => Config::from_env(),
+    }
+    .map_err(|e| format!("Configuration error: {e}"))?;
     config
         .validate()
         .map_err(|e| format!("Configuration validation error: {e}"))?;
 
+    // Optionally export spans to an OpenTelemetry collector
+    #[cfg(feature = "otel")]
+    let tracer_provider = config
+        .server
+        .otlp_endpoint
+        .as_deref()
+        .map(shared::telemetry::otlp_tracer_provider)
+        .transpose()
+        .map_err(|e| format!("Configuration error: {e}"))?;
+    #[cfg(feature = "otel")]
+    let otel_layer = tracer_provider.as_ref().map(shared::telemetry::layer);
+    #[cfg(not(feature = "otel"))]
+    let otel_layer: Option<tracing_subscriber::layer::Identity> = None;
+
     // Initialize logging with configuration
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("rust_mcp_server=debug".parse()?),
-        )
-        .init();
+    {
+        use tracing_subscriber::prelude::*;
+
+        tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::EnvFilter::from_default_env()
+                    .add_directive("rust_mcp_server=debug".parse()?),
+            )
+            .with(tracing_subscriber::fmt::layer())
+            .with(otel_layer)
+            .init();
+    }
+    #[cfg(not(feature = "otel"))]
+    if config.server.otlp_endpoint.is_some() {
+        tracing::warn!("OTLP_ENDPOINT is set but this build does not include the `otel` feature");
+    }
 
     tracing::info!("Configuration loaded successfully");
     tracing::debug!("Server config: {:?}", config.server);
 
-    let cli = Cli::parse();
-
     // Log development mode status
     if cli.dev {
         tracing::info!("🚀 Running in development mode with hot-reload enabled");
     }
 
     // Create shared state with configuration
-    let state = AppState::new();
+    let max_tool_calls = config.resource_limits.max_tool_call_history;
+    let state = match &config.server.tool_call_log_path {
+        Some(path) => AppState::with_tool_call_log(
+            path,
+            config.server.tool_call_log_compression,
+            max_tool_calls,
+        )
+        .await
+        .map_err(|e| format!("Failed to open tool call log {path}: {e}"))?,
+        None => AppState::with_history_limit(max_tool_calls),
+    };
+    let state = if config.server.tool_call_batching {
+        state.with_tool_call_batching()
+    } else {
+        state
+    };
+    let state = state
+        .with_tool_concurrency(
+            config.security.max_concurrent_tool_calls,
+            std::time::Duration::from_millis(config.security.tool_queue_timeout_ms),
+        )
+        .with_event_dedup(std::time::Duration::from_millis(
+            config.server.event_dedup_window_ms,
+        ))
+        .with_event_rate_limit(config.server.event_rate_limit_per_second)
+        .with_event_history(config.resource_limits.event_history_size);
+
+    // Record the event stream to disk before any events are sent
+    if let Some(path) = &config.server.event_log_path {
+        shared::event_log::EventLog::open(path, config.server.event_log_max_bytes)
+            .await
+            .map_err(|e| format!("Failed to open event log {path}: {e}"))?
+            .spawn(&state);
+    }
+
+    // Expire MCP sessions that go quiet
+    let session_reaper = (config.server.session_idle_timeout_seconds > 0).then(|| {
+        state.spawn_session_reaper(std::time::Duration::from_secs(
+            config.server.session_idle_timeout_seconds,
+        ))
+    });
+
+    // Stamp the MCP heartbeat while an MCP server is running
+    let heartbeat = (!matches!(cli.mode, Mode::Dashboard))
+        .then(|| state.spawn_heartbeat(shared::state::HEARTBEAT_INTERVAL));
+
+    // Record process and host resource usage as metrics gauges
+    let resource_sampler = (config.server.metrics_sample_interval_seconds > 0).then(|| {
+        shared::resource_sampler::ResourceSampler::new().spawn(
+            &state,
+            std::time::Duration::from_secs(config.server.metrics_sample_interval_seconds),
+        )
+    });
 
     // Update MCP status to show server is running
     {
@@ -90,21 +177,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Send initial events
-    let _ = state
-        .event_tx
-        .send(shared::state::SystemEvent::McpConnected);
+    state.broadcast(shared::state::SystemEvent::McpConnected);
 
     match cli.mode {
         Mode::MpcOnly => {
             println!("Starting MCP server on stdin/stdout");
 
-            // Create and run official MCP server
-            let mcp_server = server::create_mcp_server(state.clone()).await?;
-            let transport = server::create_stdio_transport();
-
-            if let Err(e) = mcp_server.run(transport).await {
-                tracing::error!("MCP server error: {}", e);
-            }
+            // Run the official MCP server, restarting it if it fails
+            let backoff = Backoff::exponential(
+                std::time::Duration::from_millis(config.server.mcp_restart_backoff_ms),
+                config.server.mcp_max_restarts,
+            );
+            let _ = supervise("MCP", RestartPolicy::OnFailure, backoff, || {
+                let (state, config) = (state.clone(), config.clone());
+                async move {
+                    let mcp_server = server::create_mcp_server(state.clone(), config).await?;
+                    track_mcp_connection(&state, mcp_server.run(server::create_stdio_transport()))
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })
+            .await;
         }
         Mode::Dashboard => {
             println!(
@@ -128,20 +221,106 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 config.server.dashboard_host, config.server.dashboard_port
             );
 
-            // Create and run official MCP server
-            let mcp_server = server::create_mcp_server(state.clone()).await?;
-            let transport = server::create_stdio_transport();
+            let grace = std::time::Duration::from_secs(config.server.shutdown_grace_seconds);
+            let mut servers: Pin<Box<dyn Future<Output = ()>>> = if config
+                .server
+                .independent_servers
+            {
+                Box::pin(run_independent_servers(state.clone(), config, cli.dev))
+            } else {
+                // Create and run official MCP server
+                let mcp_server = server::create_mcp_server(state.clone(), config.clone()).await?;
+                let mcp_state = state.clone();
+                let mcp = async move {
+                    track_mcp_connection(
+                        &mcp_state,
+                        mcp_server.run(server::create_stdio_transport()),
+                    )
+                    .await
+                };
+                let dashboard =
+                    dashboard::server::run_dashboard_with_config(state.clone(), config, cli.dev);
+
+                // Either server exiting stops both
+                Box::pin(async move {
+                    tokio::select! {
+                        _ = mcp => {},
+                        _ = dashboard => {},
+                    }
+                })
+            };
 
             // Run both servers concurrently
             tokio::select! {
-                _ = mcp_server.run(transport) => {},
-                _ = dashboard::server::run_dashboard_with_config(state, config, cli.dev) => {},
+                _ = &mut servers => {},
                 _ = tokio::signal::ctrl_c() => {
-                    tracing::info!("Received shutdown signal");
+                    tracing::info!("Received shutdown signal, draining in-flight tool calls");
+
+                    // Keep the MCP server polled so running calls can finish
+                    tokio::select! {
+                        drained = state.shutdown.shutdown(grace) => {
+                            if !drained {
+                                tracing::warn!(
+                                    "{} tool calls still running after {}s grace period",
+                                    state.shutdown.in_flight(),
+                                    grace.as_secs()
+                                );
+                            }
+                        }
+                        _ = &mut servers => {},
+                    }
                 }
             }
+
+            if state.mcp_status.load().connected {
+                state.set_mcp_connected(false);
+            }
+        }
+    }
+
+    if let Some(reaper) = session_reaper {
+        reaper.abort();
+    }
+    if let Some(sampler) = resource_sampler {
+        sampler.abort();
+    }
+    if let Some(heartbeat) = heartbeat {
+        heartbeat.abort();
+    }
+
+    // Flush spans still waiting in the batch exporter
+    #[cfg(feature = "otel")]
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            tracing::warn!("Failed to flush OpenTelemetry spans: {e}");
         }
     }
 
     Ok(())
 }
+
+/// Run the MCP and dashboard servers under supervision, so either can stop or
+/// restart without taking the other down
+async fn run_independent_servers(state: AppState, config: Config, dev: bool) {
+    let policy = config.server.server_restart_policy;
+    // A clean MCP exit means the client closed stdin, so only restart it on failure
+    let mcp_policy = match policy {
+        RestartPolicy::Always => RestartPolicy::OnFailure,
+        other => other,
+    };
+
+    let mcp = supervise("MCP", mcp_policy, RESTART_BACKOFF, || {
+        let (state, config) = (state.clone(), config.clone());
+        async move {
+            let mcp_server = server::create_mcp_server(state.clone(), config).await?;
+            track_mcp_connection(&state, mcp_server.run(server::create_stdio_transport()))
+                .await
+                .map_err(anyhow::Error::from)
+        }
+    });
+    let dashboard = supervise("dashboard", policy, RESTART_BACKOFF, || {
+        dashboard::server::run_dashboard_with_config(state.clone(), config.clone(), dev)
+    });
+
+    let _ = tokio::join!(mcp, dashboard);
+}
@@ -0,0 +1,116 @@
+//! Markdown snapshot of server state for the `debug_info` prompt.
+
+use std::fmt::Write;
+
+use crate::shared::state::{AppState, ToolCallResult};
+
+/// Number of recent tool calls included in the history section
+const DEBUG_INFO_HISTORY_LIMIT: usize = 10;
+
+/// The SDK rejects prompts longer than 10000 characters; leave room for arguments
+const DEBUG_INFO_MAX_LEN: usize = 9000;
+
+/// Describe connection status and sessions, optionally with metrics and recent tool calls
+pub async fn generate_debug_info(
+    state: &AppState,
+    include_metrics: bool,
+    include_history: bool,
+) -> String {
+    let status = state.mcp_status.load();
+    let uptime = chrono::Utc::now()
+        .signed_duration_since(status.started_at)
+        .num_seconds();
+
+    let mut markdown = String::from("# MCP Server Debug Info\n\n## Status\n\n");
+    let _ = writeln!(markdown, "- Connected: {}", status.connected);
+    let _ = writeln!(markdown, "- Uptime: {uptime}s");
+    let _ = writeln!(
+        markdown,
+        "- Last heartbeat: {}",
+        status
+            .last_heartbeat
+            .map_or_else(|| "never".to_string(), |hb| hb.to_rfc3339())
+    );
+    let _ = writeln!(
+        markdown,
+        "- Active sessions: {}",
+        state.active_sessions.len()
+    );
+
+    if include_metrics {
+        markdown.push_str("\n## Metrics\n\n");
+        let mut metrics: Vec<_> = state
+            .metrics
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().as_number()))
+            .collect();
+        metrics.sort_by(|a, b| a.0.cmp(&b.0));
+        if metrics.is_empty() {
+            markdown.push_str("No metrics recorded.\n");
+        }
+        for (name, value) in metrics {
+            let _ = writeln!(markdown, "- {name}: {value}");
+        }
+    }
+
+    if include_history {
+        markdown.push_str("\n## Recent Tool Calls\n\n");
+        let calls = state.tool_calls.read().await;
+        if calls.is_empty() {
+            markdown.push_str("No tool calls recorded.\n");
+        }
+        for call in calls.iter().rev().take(DEBUG_INFO_HISTORY_LIMIT) {
+            let outcome = match &call.result {
+                None => "pending".to_string(),
+                Some(ToolCallResult::Success(_)) => "success".to_string(),
+                Some(ToolCallResult::Error(e)) => format!("error: {e}"),
+            };
+            let _ = writeln!(
+                markdown,
+                "- {} `{}` ({}ms): {outcome}",
+                call.timestamp.to_rfc3339(),
+                call.name,
+                call.duration_ms.unwrap_or(0)
+            );
+        }
+    }
+
+    if markdown.len() > DEBUG_INFO_MAX_LEN {
+        let mut end = DEBUG_INFO_MAX_LEN;
+        while !markdown.is_char_boundary(end) {
+            end -= 1;
+        }
+        markdown.truncate(end);
+        markdown.push_str("\n…(truncated)\n");
+    }
+
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::state::ToolCall;
+
+    #[tokio::test]
+    async fn test_sections_follow_flags() {
+        let state = AppState::new();
+        state
+            .record_tool_call(ToolCall::new(
+                "file_search".to_string(),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+
+        let minimal = generate_debug_info(&state, false, false).await;
+        assert!(minimal.contains("## Status"));
+        assert!(!minimal.contains("## Metrics"));
+        assert!(!minimal.contains("## Recent Tool Calls"));
+
+        let full = generate_debug_info(&state, true, true).await;
+        assert!(full.contains("- tool_calls_file_search: 1"));
+        assert!(full.contains("`file_search`"));
+        assert!(full.contains("pending"));
+    }
+}
@@ -16,6 +16,18 @@ pub enum ToolError {
     SerializationError(String),
 }
 
+impl ToolError {
+    /// Stable machine-readable code for clients
+    pub fn code(&self) -> &'static str {
+        match self {
+            ToolError::InvalidInput(_) => "INVALID_INPUT",
+            ToolError::ToolNotFound(_) => "TOOL_NOT_FOUND",
+            ToolError::ExecutionError(_) => "TOOL_EXECUTION_FAILED",
+            ToolError::SerializationError(_) => "SERIALIZATION_ERROR",
+        }
+    }
+}
+
 // Allow dead_code: Comprehensive error type system for future functionality
 // Many variants represent planned error conditions not yet implemented
 #[allow(dead_code)]
@@ -79,11 +91,9 @@ pub enum McpServerError {
     Internal(String),
 }
 
-// Allow dead_code: Structured error handling utilities for API responses and retry logic
-// Provides error categorization, codes, and behavioral metadata
-#[allow(dead_code)]
 impl McpServerError {
-    pub fn error_code(&self) -> &'static str {
+    /// Stable machine-readable code for clients
+    pub fn code(&self) -> &'static str {
         match self {
             McpServerError::FileSystem(_) => "FILESYSTEM_ERROR",
             McpServerError::Http(_) => "HTTP_ERROR",
@@ -107,6 +117,8 @@ impl McpServerError {
         }
     }
 
+    // Allow dead_code: Error categorization for API responses and retry logic
+    #[allow(dead_code)]
     pub fn is_user_error(&self) -> bool {
         matches!(
             self,
@@ -121,6 +133,7 @@ impl McpServerError {
         )
     }
 
+    #[allow(dead_code)]
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
@@ -142,3 +155,87 @@ macro_rules! ensure {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_error_codes() {
+        let cases = [
+            (ToolError::InvalidInput(String::new()), "INVALID_INPUT"),
+            (ToolError::ToolNotFound(String::new()), "TOOL_NOT_FOUND"),
+            (
+                ToolError::ExecutionError(String::new()),
+                "TOOL_EXECUTION_FAILED",
+            ),
+            (
+                ToolError::SerializationError(String::new()),
+                "SERIALIZATION_ERROR",
+            ),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.code(), code);
+        }
+    }
+
+    #[test]
+    fn test_server_error_codes() {
+        let io = std::io::Error::other("disk");
+        let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let cases = [
+            (McpServerError::FileSystem(io), "FILESYSTEM_ERROR"),
+            (McpServerError::State(String::new()), "STATE_ERROR"),
+            (McpServerError::Timeout, "TIMEOUT"),
+            (
+                McpServerError::Permission(String::new()),
+                "PERMISSION_DENIED",
+            ),
+            (McpServerError::InvalidPath(String::new()), "INVALID_PATH"),
+            (
+                McpServerError::FileSizeLimit { limit: 1 },
+                "FILE_SIZE_LIMIT",
+            ),
+            (
+                McpServerError::RateLimit {
+                    endpoint: String::new(),
+                },
+                "RATE_LIMIT",
+            ),
+            (McpServerError::Config(String::new()), "CONFIGURATION_ERROR"),
+            (McpServerError::Serialization(json), "SERIALIZATION_ERROR"),
+            (McpServerError::System(String::new()), "SYSTEM_ERROR"),
+            (
+                McpServerError::ResourceNotFound(String::new()),
+                "RESOURCE_NOT_FOUND",
+            ),
+            (
+                McpServerError::ToolNotFound(String::new()),
+                "TOOL_NOT_FOUND",
+            ),
+            (
+                McpServerError::ToolExecution(String::new()),
+                "TOOL_EXECUTION_FAILED",
+            ),
+            (
+                McpServerError::PromptNotFound {
+                    name: String::new(),
+                },
+                "PROMPT_NOT_FOUND",
+            ),
+            (
+                McpServerError::InvalidArguments(String::new()),
+                "INVALID_ARGUMENTS",
+            ),
+            (McpServerError::Protocol(String::new()), "PROTOCOL_ERROR"),
+            (
+                McpServerError::Authentication(String::new()),
+                "AUTHENTICATION_ERROR",
+            ),
+            (McpServerError::Internal(String::new()), "INTERNAL_ERROR"),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.code(), code, "{error}");
+        }
+    }
+}
@@ -1,37 +1,313 @@
-use std::{future::Future, pin::Pin};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
-use mcp_server::router::{CapabilitiesBuilder, Router};
+use base64::Engine;
+use mcp_server::{
+    router::{CapabilitiesBuilder, Router},
+    RouterError,
+};
 use mcp_spec::{
     content::{Content, TextContent},
     handler::{PromptError, ResourceError, ToolError},
-    prompt::Prompt,
-    protocol::ServerCapabilities,
-    resource::Resource,
+    prompt::{Prompt, PromptArgument},
+    protocol::{JsonRpcRequest, JsonRpcResponse, ServerCapabilities},
+    resource::{Resource, ResourceContents},
     tool::Tool,
 };
 use serde_json::Value;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::server::debug_info::generate_debug_info;
+use crate::shared::{
+    config::{Config, PromptTemplateConfig},
+    state::{gzip, AppState, SystemEvent},
+};
+use crate::tools::{
+    circuit_breaker::ToolCircuitBreaker, metrics::GetMetricsTool, pool::ToolWorkerPool,
+    ToolRegistry,
+};
+
+/// URI of the resource describing every registered tool, including output schemas
+const TOOL_REGISTRY_URI: &str = "tools://registry";
+
+/// URI of the resource reporting effective limits
+const LIMITS_URI: &str = "limits://effective";
+
+/// URI of the resource exposing the server configuration
+const CONFIG_URI: &str = "config://server";
 
-use crate::shared::state::AppState;
-use crate::tools::{file_search::FileSearchTool, ToolRegistry};
+/// URI of the resource listing recent system events
+const LOGS_URI: &str = "logs://recent";
+
+/// Number of events returned by the recent logs resource
+const RECENT_LOGS_LIMIT: usize = 100;
+
+/// Name of the built-in prompt describing server state
+const DEBUG_INFO_PROMPT: &str = "debug_info";
+
+/// MIME type marking a tool result block as gzip-compressed, base64-encoded text
+const GZIP_MIME_TYPE: &str = "application/gzip";
 
 #[derive(Clone)]
 pub struct McpRouter {
     state: AppState,
     tool_registry: ToolRegistry,
+    tool_pool: Option<ToolWorkerPool>,
+    circuit_breaker: Option<ToolCircuitBreaker>,
+    tool_reflection: bool,
+    tool_examples: bool,
+    structured_content: bool,
+    /// Text blocks larger than this many bytes are gzipped; 0 disables compression
+    compression_threshold: u64,
+    /// Limits report served as a resource, if enabled
+    limits_json: Option<String>,
+    /// Configuration served as a resource
+    config_json: String,
+    /// Prompt templates from the config file
+    prompts: Vec<PromptTemplateConfig>,
+    /// Session for this connection, replaced if it expires while idle
+    session: Arc<Mutex<Option<Uuid>>>,
 }
 
 impl McpRouter {
+    // Allow dead_code: Convenience constructor using default configuration
+    #[allow(dead_code)]
     pub fn new(state: AppState) -> Self {
-        let mut tool_registry = ToolRegistry::new();
+        Self::with_config(state, Config::default())
+    }
 
-        // Register available tools
-        tool_registry.register(FileSearchTool);
+    pub fn with_config(state: AppState, config: Config) -> Self {
+        // The registry records each call, so the pool's workers do too
+        let mut tool_registry = ToolRegistry::with_default_tools(&config).with_state(state.clone());
+        tool_registry.register(GetMetricsTool::new(state.clone()));
+
+        // Optionally route calls through a warm pool of worker tasks
+        let pool_size = config.resource_limits.tool_worker_pool_size;
+        let tool_pool =
+            (pool_size > 0).then(|| ToolWorkerPool::new(tool_registry.clone(), pool_size));
+
+        // Optionally disable tools that keep failing
+        let failure_threshold = config.security.tool_failure_threshold;
+        let circuit_breaker = (failure_threshold > 0).then(|| {
+            ToolCircuitBreaker::new(
+                failure_threshold,
+                std::time::Duration::from_millis(config.security.tool_failure_cooldown_ms),
+            )
+        });
 
         Self {
             state,
             tool_registry,
+            tool_pool,
+            circuit_breaker,
+            tool_reflection: config.server.enable_tool_reflection,
+            tool_examples: config.server.enable_tool_examples,
+            structured_content: config.server.structured_tool_content,
+            compression_threshold: config.server.tool_result_compression_threshold_bytes,
+            limits_json: config.server.enable_limits_reporting.then(|| {
+                serde_json::to_string_pretty(&config.limits_report())
+                    .unwrap_or_else(|_| "{}".to_string())
+            }),
+            config_json: serde_json::to_string_pretty(&config.redacted())
+                .unwrap_or_else(|_| "{}".to_string()),
+            prompts: config.prompts,
+            session: Arc::new(Mutex::new(None)),
         }
     }
+
+    /// Start the session for a newly established connection
+    pub fn start_session(&self) -> Uuid {
+        let id = self.state.start_session();
+        *self.session.lock().unwrap_or_else(|e| e.into_inner()) = Some(id);
+        id
+    }
+
+    /// Record activity on this connection's session, starting a new one if
+    /// there is none or it expired
+    fn touch_session(&self) {
+        let mut session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+        let current = session.filter(|id| self.state.touch_session(*id));
+        if current.is_none() {
+            let id = self.state.start_session();
+            self.state.touch_session(id);
+            *session = Some(id);
+        }
+    }
+
+    /// Serialize the tool registry, including output schemas, sorted by name.
+    ///
+    /// The MCP `Tool` listing only carries `input_schema`, so clients that want
+    /// output schemas read them from this resource instead.
+    fn tool_registry_json(&self) -> String {
+        let mut tools = self.tool_registry.list_tools();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+        serde_json::to_string_pretty(&serde_json::json!({ "tools": tools }))
+            .unwrap_or_else(|_| "{\"tools\":[]}".to_string())
+    }
+}
+
+/// Remove the optional `_meta` object (`caller`, `tags`) from tool arguments.
+///
+/// Metadata is recorded on the `ToolCall` for attribution and never reaches the tool.
+fn split_call_metadata(mut arguments: Value) -> (Value, Option<String>, Vec<String>) {
+    let meta = arguments
+        .as_object_mut()
+        .and_then(|args| args.remove("_meta"))
+        .unwrap_or(Value::Null);
+
+    let caller = meta
+        .get("caller")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let tags = meta
+        .get("tags")
+        .and_then(Value::as_array)
+        .map(|tags| {
+            tags.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    (arguments, caller, tags)
+}
+
+/// Resources the router serves, depending on which optional ones are enabled
+fn resources(tool_reflection: bool, limits: bool) -> Vec<Resource> {
+    let mut resources = Vec::new();
+
+    if tool_reflection {
+        resources.extend(
+            Resource::with_uri(TOOL_REGISTRY_URI, "tool-registry", 0.5, None).map(|resource| {
+                resource
+                    .with_description("Registered tools with their input and output JSON schemas")
+            }),
+        );
+    }
+
+    if limits {
+        resources.extend(
+            Resource::with_uri(LIMITS_URI, "limits", 0.5, None).map(|resource| {
+                resource.with_description("Effective file size, allowlist, timeout and rate limits")
+            }),
+        );
+    }
+
+    resources.extend(
+        Resource::with_uri(CONFIG_URI, "server-config", 0.5, None)
+            .map(|resource| resource.with_description("Effective server configuration")),
+    );
+    resources.extend(
+        Resource::with_uri(LOGS_URI, "recent-logs", 0.5, None)
+            .map(|resource| resource.with_description("Most recent system events, newest first")),
+    );
+
+    resources
+}
+
+/// Number of resources a router built from `config` serves
+pub fn resource_count(config: &Config) -> usize {
+    resources(
+        config.server.enable_tool_reflection,
+        config.server.enable_limits_reporting,
+    )
+    .len()
+}
+
+/// Render a tool result as a single text block
+fn text_content(result: Value) -> Content {
+    let result_text = match result {
+        Value::String(s) => s,
+        _ => serde_json::to_string_pretty(&result)
+            .unwrap_or_else(|_| "Tool executed successfully".to_string()),
+    };
+
+    Content::Text(TextContent {
+        text: result_text,
+        annotations: None,
+    })
+}
+
+/// Replace text blocks over `threshold` bytes with gzip-compressed blobs.
+///
+/// Compressed blocks are embedded blob resources whose `mimeType` is
+/// `application/gzip`; the blob is the base64 of the gzipped UTF-8 text.
+/// Blocks that would not get smaller stay as text, and a `threshold` of 0
+/// disables compression.
+fn compress_large_content(content: Vec<Content>, threshold: u64, call_id: &str) -> Vec<Content> {
+    if threshold == 0 {
+        return content;
+    }
+
+    content
+        .into_iter()
+        .enumerate()
+        .map(|(index, block)| match block {
+            Content::Text(text) if text.text.len() as u64 > threshold => {
+                let blob = gzip(text.text.as_bytes())
+                    .map(|compressed| base64::engine::general_purpose::STANDARD.encode(compressed));
+                match blob {
+                    Ok(blob) if blob.len() < text.text.len() => {
+                        Content::resource(ResourceContents::BlobResourceContents {
+                            uri: format!("tool-result://{call_id}/{index}"),
+                            mime_type: Some(GZIP_MIME_TYPE.to_string()),
+                            blob,
+                        })
+                    }
+                    _ => Content::Text(text),
+                }
+            }
+            other => other,
+        })
+        .collect()
+}
+
+/// Split a structured tool result into separate MCP content blocks.
+///
+/// Scalar fields are summarized in a leading block (with a `<field>_count` for
+/// each array) and every array element becomes its own block. Elements that
+/// name a `file_path` are embedded as `file://` resources.
+fn structured_content_blocks(result: Value) -> Vec<Content> {
+    let item_content = |item: Value| match item.get("file_path").and_then(Value::as_str) {
+        Some(path) => Content::embedded_text(
+            format!("file://{path}"),
+            serde_json::to_string_pretty(&item).unwrap_or_default(),
+        ),
+        None => text_content(item),
+    };
+
+    match result {
+        Value::Object(fields) => {
+            let mut summary = serde_json::Map::new();
+            let mut items = Vec::new();
+
+            for (key, value) in fields {
+                match value {
+                    Value::Array(elements) => {
+                        summary.insert(format!("{key}_count"), Value::from(elements.len()));
+                        items.extend(elements);
+                    }
+                    other => {
+                        summary.insert(key, other);
+                    }
+                }
+            }
+
+            std::iter::once(text_content(Value::Object(summary)))
+                .chain(items.into_iter().map(item_content))
+                .collect()
+        }
+        Value::Array(elements) if !elements.is_empty() => {
+            elements.into_iter().map(item_content).collect()
+        }
+        other => vec![text_content(other)],
+    }
 }
 
 impl Router for McpRouter {
@@ -49,8 +325,8 @@ impl Router for McpRouter {
     fn capabilities(&self) -> ServerCapabilities {
         CapabilitiesBuilder::new()
             .with_tools(true)
-            .with_resources(false, false)
-            .with_prompts(false)
+            .with_resources(true, false)
+            .with_prompts(true)
             .build()
     }
 
@@ -58,10 +334,20 @@ impl Router for McpRouter {
         self.tool_registry
             .list_tools()
             .into_iter()
-            .map(|tool_info| Tool {
-                name: tool_info.name,
-                description: tool_info.description,
-                input_schema: tool_info.input_schema,
+            .map(|tool_info| {
+                let mut input_schema = tool_info.input_schema;
+                // Sample invocations ride along as the standard JSON Schema `examples` keyword
+                if self.tool_examples && !tool_info.examples.is_empty() {
+                    if let Some(schema) = input_schema.as_object_mut() {
+                        schema.insert("examples".to_string(), Value::from(tool_info.examples));
+                    }
+                }
+
+                Tool {
+                    name: tool_info.name,
+                    description: tool_info.description,
+                    input_schema,
+                }
             })
             .collect()
     }
@@ -73,80 +359,117 @@ impl Router for McpRouter {
     ) -> Pin<Box<dyn Future<Output = Result<Vec<Content>, ToolError>> + Send + 'static>> {
         let state = self.state.clone();
         let tool_registry = self.tool_registry.clone();
+        let tool_pool = self.tool_pool.clone();
+        let circuit_breaker = self.circuit_breaker.clone();
+        let structured_content = self.structured_content;
+        let compression_threshold = self.compression_threshold;
+        self.touch_session();
         let tool_name = tool_name.to_string();
-        let arguments = arguments.clone();
+        let (arguments, caller, tags) = split_call_metadata(arguments);
+        let span = tracing::info_span!(
+            "tool_call",
+            tool.name = %tool_name,
+            tool.duration_ms = tracing::field::Empty,
+            tool.success = tracing::field::Empty,
+        );
 
-        Box::pin(async move {
-            let start_time = std::time::Instant::now();
-
-            // Log the tool call start
-            let tool_call =
-                crate::shared::state::ToolCall::new(tool_name.clone(), arguments.clone());
-            let _tool_call_id = tool_call.id;
-            let _ = state.record_tool_call(tool_call).await;
-
-            // Execute the tool
-            match tool_registry.call_tool(&tool_name, arguments.clone()).await {
-                Ok(result) => {
-                    let duration = start_time.elapsed().as_millis() as u64;
-
-                    // Update tool call with success
-                    let completed_call =
-                        crate::shared::state::ToolCall::new(tool_name.clone(), arguments.clone())
-                            .complete(
-                                crate::shared::state::ToolCallResult::Success(result.clone()),
-                                duration,
-                            );
-                    let _ = state.record_tool_call(completed_call).await;
-
-                    // Format result for MCP response
-                    let result_text = match result {
-                        Value::String(s) => s,
-                        _ => serde_json::to_string_pretty(&result)
-                            .unwrap_or_else(|_| "Tool executed successfully".to_string()),
-                    };
-
-                    Ok(vec![Content::Text(TextContent {
-                        text: result_text,
-                        annotations: None,
-                    })])
-                }
-                Err(e) => {
-                    let duration = start_time.elapsed().as_millis() as u64;
-
-                    // Update tool call with error
-                    let failed_call =
-                        crate::shared::state::ToolCall::new(tool_name.clone(), arguments.clone())
-                            .complete(
-                                crate::shared::state::ToolCallResult::Error(e.to_string()),
-                                duration,
-                            );
-                    let _ = state.record_tool_call(failed_call).await;
-
-                    // Convert our tool error to MCP tool error
-                    let mcp_error = match e {
-                        crate::server::error::ToolError::InvalidInput(msg) => {
-                            ToolError::InvalidParameters(msg)
-                        }
-                        crate::server::error::ToolError::ToolNotFound(msg) => {
-                            ToolError::InvalidParameters(format!("Tool not found: {msg}"))
-                        }
-                        crate::server::error::ToolError::ExecutionError(msg) => {
-                            ToolError::ExecutionError(msg)
-                        }
-                        crate::server::error::ToolError::SerializationError(msg) => {
-                            ToolError::ExecutionError(format!("Serialization error: {msg}"))
+        Box::pin(
+            async move {
+                // Refuse new calls during shutdown; the guard keeps this one counted until it returns
+                let Some(_in_flight) = state.shutdown.begin_call() else {
+                    return Err(ToolError::ExecutionError(
+                        "Server is shutting down".to_string(),
+                    ));
+                };
+
+                // Wait for a free slot, or reject the call so the client backs off
+                let _permit = state
+                    .acquire_tool_permit(&tool_name)
+                    .await
+                    .map_err(|e| ToolError::ExecutionError(format!("[{}] {e}", e.code())))?;
+
+                let start_time = std::time::Instant::now();
+
+                // The registry records the call; its id names compressed results
+                let tool_call = crate::shared::state::ToolCall::new(tool_name.clone(), arguments)
+                    .with_metadata(caller, tags);
+                let call_id = tool_call.id;
+
+                // Execute the tool, unless its circuit breaker has disabled it
+                let call_result = match circuit_breaker.as_ref().map(|b| b.check(&tool_name)) {
+                    Some(Err(e)) => tool_registry.reject(tool_call, e).await,
+                    _ => {
+                        let result = match &tool_pool {
+                            Some(pool) => pool.execute(tool_call).await,
+                            None => tool_registry.execute(tool_call).await,
+                        };
+
+                        let tripped = circuit_breaker
+                            .as_ref()
+                            .is_some_and(|breaker| breaker.record(&tool_name, &result));
+                        if tripped {
+                            state.broadcast(crate::shared::state::SystemEvent::Error {
+                                message: format!(
+                                    "Tool '{tool_name}' disabled after repeated failures"
+                                ),
+                            });
                         }
-                    };
 
-                    Err(mcp_error)
+                        result
+                    }
+                };
+
+                let duration = start_time.elapsed().as_millis() as u64;
+                let span = tracing::Span::current();
+                span.record("tool.duration_ms", duration);
+                span.record("tool.success", call_result.is_ok());
+
+                match call_result {
+                    Ok(result) => {
+                        // Format result for MCP response
+                        let content = if structured_content {
+                            structured_content_blocks(result)
+                        } else {
+                            vec![text_content(result)]
+                        };
+                        Ok(compress_large_content(
+                            content,
+                            compression_threshold,
+                            &call_id.to_string(),
+                        ))
+                    }
+                    Err(e) => {
+                        // Convert our tool error to MCP tool error, led by its code
+                        let code = e.code();
+                        let mcp_error = match e {
+                            crate::server::error::ToolError::InvalidInput(msg) => {
+                                ToolError::InvalidParameters(format!("[{code}] {msg}"))
+                            }
+                            crate::server::error::ToolError::ToolNotFound(msg) => {
+                                ToolError::InvalidParameters(format!(
+                                    "[{code}] Tool not found: {msg}"
+                                ))
+                            }
+                            crate::server::error::ToolError::ExecutionError(msg) => {
+                                ToolError::ExecutionError(format!("[{code}] {msg}"))
+                            }
+                            crate::server::error::ToolError::SerializationError(msg) => {
+                                ToolError::ExecutionError(format!(
+                                    "[{code}] Serialization error: {msg}"
+                                ))
+                            }
+                        };
+
+                        Err(mcp_error)
+                    }
                 }
             }
-        })
+            .instrument(span),
+        )
     }
 
     fn list_resources(&self) -> Vec<Resource> {
-        vec![]
+        resources(self.tool_reflection, self.limits_json.is_some())
     }
 
     fn read_resource(
@@ -154,22 +477,738 @@ impl Router for McpRouter {
         uri: &str,
     ) -> Pin<Box<dyn Future<Output = Result<String, ResourceError>> + Send + 'static>> {
         let uri = uri.to_string();
+        let contents = match uri.as_str() {
+            TOOL_REGISTRY_URI if self.tool_reflection => Some(self.tool_registry_json()),
+            LIMITS_URI => self.limits_json.clone(),
+            CONFIG_URI => Some(self.config_json.clone()),
+            _ => None,
+        };
+        // Recent logs are read from the event history once the future runs
+        let is_logs = uri == LOGS_URI;
+
+        // Let the dashboard's resource panel show the read
+        if contents.is_some() || is_logs {
+            self.state
+                .broadcast(SystemEvent::ResourceAccessed { uri: uri.clone() });
+        }
+
+        let state = self.state.clone();
         Box::pin(async move {
-            Err(ResourceError::NotFound(format!(
-                "Resource not found: {uri}"
-            )))
+            if is_logs {
+                let events = state.get_events(RECENT_LOGS_LIMIT).await;
+                return serde_json::to_string_pretty(&events)
+                    .map_err(|e| ResourceError::ExecutionError(e.to_string()));
+            }
+            contents.ok_or_else(|| ResourceError::NotFound(format!("Resource not found: {uri}")))
         })
     }
 
     fn list_prompts(&self) -> Vec<Prompt> {
-        vec![]
+        let debug_info = Prompt::new(
+            DEBUG_INFO_PROMPT,
+            Some("Snapshot of connection status, sessions, metrics and recent tool calls"),
+            Some(vec![
+                PromptArgument {
+                    name: "include_metrics".to_string(),
+                    description: Some(
+                        "Whether to include the metrics section (default true)".to_string(),
+                    ),
+                    required: Some(false),
+                },
+                PromptArgument {
+                    name: "include_history".to_string(),
+                    description: Some(
+                        "Whether to include the recent tool calls section (default true)"
+                            .to_string(),
+                    ),
+                    required: Some(false),
+                },
+            ]),
+        );
+
+        std::iter::once(debug_info)
+            .chain(
+                self.prompts
+                    .iter()
+                    .filter(|prompt| prompt.name != DEBUG_INFO_PROMPT)
+                    .map(|prompt| {
+                        let arguments = prompt
+                            .arguments
+                            .iter()
+                            .map(|argument| PromptArgument {
+                                name: argument.name.clone(),
+                                description: argument.description.clone(),
+                                required: Some(argument.required),
+                            })
+                            .collect();
+                        Prompt::new(&prompt.name, prompt.description.as_deref(), Some(arguments))
+                    }),
+            )
+            .collect()
     }
 
+    /// Returns the raw template; the SDK fills in `{argument}` placeholders
     fn get_prompt(
         &self,
         name: &str,
     ) -> Pin<Box<dyn Future<Output = Result<String, PromptError>> + Send + 'static>> {
+        self.prompt(name, DebugInfoSections::default())
+    }
+
+    fn handle_prompts_get(
+        &self,
+        req: JsonRpcRequest,
+    ) -> impl Future<Output = Result<JsonRpcResponse, RouterError>> + Send {
+        // The SDK does not pass argument values to `get_prompt`, so read the
+        // `debug_info` flags here and let the default handler do the rest
+        let prompts = PromptRequest {
+            router: self.clone(),
+            sections: DebugInfoSections::from_request(&req),
+        };
+        async move { prompts.handle_prompts_get(req).await }
+    }
+}
+
+/// Sections the `debug_info` prompt includes, from its `include_*` arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DebugInfoSections {
+    metrics: bool,
+    history: bool,
+}
+
+impl Default for DebugInfoSections {
+    fn default() -> Self {
+        Self {
+            metrics: true,
+            history: true,
+        }
+    }
+}
+
+impl DebugInfoSections {
+    /// Read the flags from a `prompts/get` request; missing or unparsable
+    /// flags include their section
+    fn from_request(req: &JsonRpcRequest) -> Self {
+        let arguments = req
+            .params
+            .as_ref()
+            .and_then(|params| params.get("arguments"));
+        let flag = |name: &str| {
+            arguments
+                .and_then(|arguments| arguments.get(name))
+                .and_then(Value::as_str)
+                .and_then(|value| value.trim().to_ascii_lowercase().parse().ok())
+                .unwrap_or(true)
+        };
+        Self {
+            metrics: flag("include_metrics"),
+            history: flag("include_history"),
+        }
+    }
+}
+
+impl McpRouter {
+    /// Prompt text for `name`, rendering `debug_info` with `sections`
+    fn prompt(
+        &self,
+        name: &str,
+        sections: DebugInfoSections,
+    ) -> Pin<Box<dyn Future<Output = Result<String, PromptError>> + Send + 'static>> {
+        if name == DEBUG_INFO_PROMPT {
+            let state = self.state.clone();
+            return Box::pin(async move {
+                let info = generate_debug_info(&state, sections.metrics, sections.history).await;
+                Ok(format!("Help me debug this MCP server.\n\n{info}"))
+            });
+        }
+
+        let template = self
+            .prompts
+            .iter()
+            .find(|prompt| prompt.name == name)
+            .map(|prompt| prompt.template.clone());
         let name = name.to_string();
-        Box::pin(async move { Err(PromptError::NotFound(format!("Prompt not found: {name}"))) })
+        Box::pin(async move {
+            template.ok_or_else(|| PromptError::NotFound(format!("Prompt not found: {name}")))
+        })
+    }
+}
+
+/// A router answering one `prompts/get` request with its `debug_info` flags.
+///
+/// Runs the SDK's default `handle_prompts_get`, which [`McpRouter`] overrides.
+struct PromptRequest {
+    router: McpRouter,
+    sections: DebugInfoSections,
+}
+
+impl Router for PromptRequest {
+    fn name(&self) -> String {
+        self.router.name()
+    }
+
+    fn instructions(&self) -> String {
+        self.router.instructions()
+    }
+
+    fn capabilities(&self) -> ServerCapabilities {
+        self.router.capabilities()
+    }
+
+    fn list_tools(&self) -> Vec<Tool> {
+        self.router.list_tools()
+    }
+
+    fn call_tool(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<Content>, ToolError>> + Send + 'static>> {
+        self.router.call_tool(tool_name, arguments)
+    }
+
+    fn list_resources(&self) -> Vec<Resource> {
+        self.router.list_resources()
+    }
+
+    fn read_resource(
+        &self,
+        uri: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<String, ResourceError>> + Send + 'static>> {
+        self.router.read_resource(uri)
+    }
+
+    fn list_prompts(&self) -> Vec<Prompt> {
+        self.router.list_prompts()
+    }
+
+    fn get_prompt(
+        &self,
+        name: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<String, PromptError>> + Send + 'static>> {
+        self.router.prompt(name, self.sections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn router(enable_tool_reflection: bool) -> McpRouter {
+        let mut config = Config::default();
+        config.server.enable_tool_reflection = enable_tool_reflection;
+        McpRouter::with_config(AppState::new(), config)
+    }
+
+    #[tokio::test]
+    async fn test_tool_registry_resource_carries_output_schemas() {
+        let router = router(true);
+
+        let resources = router.list_resources();
+        assert!(resources.iter().any(|r| r.uri == TOOL_REGISTRY_URI));
+
+        let body = router.read_resource(TOOL_REGISTRY_URI).await.unwrap();
+        let registry: Value = serde_json::from_str(&body).unwrap();
+        let tools = registry["tools"].as_array().unwrap();
+
+        assert_eq!(tools.len(), router.list_tools().len());
+        for tool in tools {
+            assert!(tool["input_schema"].is_object());
+            assert!(
+                tool["output_schema"]["properties"].is_object(),
+                "{} should expose its output schema",
+                tool["name"]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resource_reads_emit_events() {
+        let router = router(true);
+        let mut events = router.state.subscribe_to_events();
+
+        router.read_resource(TOOL_REGISTRY_URI).await.unwrap();
+        assert_eq!(
+            events.try_recv().unwrap(),
+            SystemEvent::ResourceAccessed {
+                uri: TOOL_REGISTRY_URI.to_string()
+            }
+        );
+
+        // Unknown resources are not reported as accessed
+        assert!(router.read_resource("missing://nothing").await.is_err());
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_limits_resource_enabled_by_config() {
+        let hidden = McpRouter::with_config(AppState::new(), Config::default());
+        assert!(!hidden.list_resources().iter().any(|r| r.uri == LIMITS_URI));
+        assert!(hidden.read_resource(LIMITS_URI).await.is_err());
+
+        let mut config = Config::default();
+        config.server.enable_limits_reporting = true;
+        config.security.max_file_size_bytes = 4096;
+        let router = McpRouter::with_config(AppState::new(), config);
+
+        let resources = router.list_resources();
+        assert!(resources.iter().any(|r| r.uri == LIMITS_URI));
+
+        let limits: Value =
+            serde_json::from_str(&router.read_resource(LIMITS_URI).await.unwrap()).unwrap();
+        assert_eq!(limits["max_file_size_bytes"], 4096);
+    }
+
+    #[tokio::test]
+    async fn test_config_and_logs_resources() {
+        let mut config = Config::default();
+        config.server.dashboard_port = 4321;
+        let state = AppState::new().with_event_history(10);
+        let router = McpRouter::with_config(state.clone(), config);
+        assert!(router.capabilities().resources.is_some());
+
+        let uris: Vec<_> = router.list_resources().into_iter().map(|r| r.uri).collect();
+        assert!(uris.contains(&CONFIG_URI.to_string()));
+        assert!(uris.contains(&LOGS_URI.to_string()));
+
+        let served: Value =
+            serde_json::from_str(&router.read_resource(CONFIG_URI).await.unwrap()).unwrap();
+        assert_eq!(served["server"]["dashboard_port"], 4321);
+
+        state.broadcast(SystemEvent::McpConnected);
+        for _ in 0..100 {
+            if !state.get_events(1).await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let logs: Value =
+            serde_json::from_str(&router.read_resource(LOGS_URI).await.unwrap()).unwrap();
+        let logs = logs.as_array().unwrap();
+        assert!(logs
+            .iter()
+            .any(|recorded| recorded["event"] == serde_json::json!("McpConnected")));
+    }
+
+    #[test]
+    fn test_tool_examples_listed_when_enabled() {
+        let file_search_schema = |router: &McpRouter| {
+            router
+                .list_tools()
+                .into_iter()
+                .find(|tool| tool.name == "file_search")
+                .unwrap()
+                .input_schema
+        };
+
+        let hidden = McpRouter::with_config(AppState::new(), Config::default());
+        assert!(file_search_schema(&hidden).get("examples").is_none());
+
+        let mut config = Config::default();
+        config.server.enable_tool_examples = true;
+        let shown = McpRouter::with_config(AppState::new(), config);
+        let examples = file_search_schema(&shown)["examples"].clone();
+        assert!(!examples.as_array().unwrap().is_empty());
+        assert!(examples[0]["pattern"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_calls_refused_during_shutdown() {
+        let state = AppState::new();
+        let router = McpRouter::with_config(state.clone(), Config::default());
+
+        assert!(
+            state
+                .shutdown
+                .shutdown(std::time::Duration::from_millis(10))
+                .await
+        );
+
+        let result = router
+            .call_tool("file_search", serde_json::json!({ "pattern": "x" }))
+            .await;
+        assert!(matches!(result, Err(ToolError::ExecutionError(_))));
+        assert!(state.tool_calls.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_recorded_once_and_completed_in_place() {
+        let state = AppState::new();
+        let router = McpRouter::with_config(state.clone(), Config::default());
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        router
+            .call_tool(
+                "file_search",
+                serde_json::json!({ "pattern": "x", "directory": temp_dir.path() }),
+            )
+            .await
+            .expect("file_search should succeed");
+
+        let calls = state.tool_calls.read().await;
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].success);
+        assert!(matches!(
+            calls[0].result,
+            Some(crate::shared::state::ToolCallResult::Success(_))
+        ));
+        assert!(calls[0].duration_ms.is_some());
+        assert_eq!(
+            state
+                .metrics
+                .get("tool_calls_file_search")
+                .unwrap()
+                .as_number(),
+            1.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_call_metadata_recorded_and_stripped() {
+        let state = AppState::new();
+        let router = McpRouter::with_config(state.clone(), Config::default());
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        router
+            .call_tool(
+                "file_search",
+                serde_json::json!({
+                    "pattern": "x",
+                    "directory": temp_dir.path(),
+                    "_meta": { "caller": "agent-a", "tags": ["nightly"] }
+                }),
+            )
+            .await
+            .expect("file_search should succeed");
+
+        let calls = state.get_tool_calls_by_caller("agent-a", 10).await;
+        assert_eq!(calls.len(), 1);
+        assert!(calls.iter().all(|c| c.tags == vec!["nightly".to_string()]));
+        assert!(calls[0].arguments.get("_meta").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pooled_call_recorded_once_with_metadata() {
+        let state = AppState::new();
+        let mut config = Config::default();
+        config.resource_limits.tool_worker_pool_size = 2;
+        let router = McpRouter::with_config(state.clone(), config);
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        router
+            .call_tool(
+                "file_search",
+                serde_json::json!({
+                    "pattern": "x",
+                    "directory": temp_dir.path(),
+                    "_meta": { "caller": "agent-b" }
+                }),
+            )
+            .await
+            .expect("file_search should succeed");
+
+        let calls = state.tool_calls.read().await;
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].caller.as_deref(), Some("agent-b"));
+        assert!(calls[0].success);
+        assert_eq!(
+            state
+                .metrics
+                .get("tool_calls_file_search")
+                .unwrap()
+                .as_number(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_structured_output_produces_multiple_blocks() {
+        let result = serde_json::json!({
+            "matches": [
+                { "file_path": "/tmp/a.txt", "line_number": 1 },
+                { "file_path": "/tmp/b.txt", "line_number": 7 }
+            ],
+            "truncated": false
+        });
+
+        let blocks = structured_content_blocks(result.clone());
+        assert_eq!(blocks.len(), 3);
+
+        let summary: Value = serde_json::from_str(blocks[0].as_text().unwrap()).unwrap();
+        assert_eq!(summary["matches_count"], 2);
+        assert_eq!(summary["truncated"], false);
+
+        match &blocks[1] {
+            Content::Resource(embedded) => {
+                let item: Value = serde_json::from_str(&embedded.get_text()).unwrap();
+                assert_eq!(item["file_path"], "/tmp/a.txt");
+            }
+            other => panic!("expected an embedded resource, got {other:?}"),
+        }
+
+        // Without structured output the result stays a single text blob
+        assert!(text_content(result).as_text().is_some());
+        assert_eq!(
+            structured_content_blocks(Value::String("done".to_string())).len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_registry_resource_disabled_by_default() {
+        let router = router(false);
+
+        let resources = router.list_resources();
+        assert!(resources.iter().all(|r| r.uri != TOOL_REGISTRY_URI));
+        assert!(matches!(
+            router.read_resource(TOOL_REGISTRY_URI).await,
+            Err(ResourceError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_config_prompt_listed_and_rendered() {
+        use crate::shared::config::PromptArgumentConfig;
+
+        let mut config = Config::default();
+        config.prompts.push(PromptTemplateConfig {
+            name: "review".to_string(),
+            description: Some("Review a file".to_string()),
+            arguments: vec![PromptArgumentConfig {
+                name: "path".to_string(),
+                description: None,
+                required: true,
+            }],
+            template: "Review {path} for {focus} issues".to_string(),
+        });
+        let router = McpRouter::with_config(AppState::new(), config);
+
+        let prompts = router.list_prompts();
+        let review = prompts.iter().find(|p| p.name == "review").unwrap();
+        assert_eq!(review.arguments.as_ref().unwrap()[0].required, Some(true));
+
+        let request = |arguments: Value| mcp_spec::protocol::JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(1),
+            method: "prompts/get".to_string(),
+            params: Some(serde_json::json!({ "name": "review", "arguments": arguments })),
+        };
+
+        let response = router
+            .handle_prompts_get(request(
+                serde_json::json!({ "path": "src/main.rs", "focus": "safety" }),
+            ))
+            .await
+            .unwrap();
+        let text = &response.result.unwrap()["messages"][0]["content"]["text"];
+        assert_eq!(text, "Review src/main.rs for safety issues");
+
+        // Required arguments are enforced
+        assert!(router
+            .handle_prompts_get(request(serde_json::json!({ "focus": "safety" })))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_debug_info_prompt() {
+        let router = McpRouter::with_config(AppState::new(), Config::default());
+        assert!(router.capabilities().prompts.is_some());
+
+        let prompts = router.list_prompts();
+        let debug_info = prompts
+            .iter()
+            .find(|p| p.name == DEBUG_INFO_PROMPT)
+            .unwrap();
+        let arguments: Vec<_> = debug_info
+            .arguments
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|argument| argument.name.as_str())
+            .collect();
+        assert_eq!(arguments, ["include_metrics", "include_history"]);
+
+        let response = router
+            .handle_prompts_get(mcp_spec::protocol::JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                id: Some(1),
+                method: "prompts/get".to_string(),
+                params: Some(serde_json::json!({
+                    "name": DEBUG_INFO_PROMPT,
+                    "arguments": { "include_metrics": "true", "include_history": "false" }
+                })),
+            })
+            .await
+            .unwrap();
+        let text = response.result.unwrap()["messages"][0]["content"]["text"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(text.contains("## Status"));
+        assert!(text.contains("## Metrics"));
+        assert!(!text.contains("## Recent Tool Calls"));
+    }
+
+    #[tokio::test]
+    async fn test_debug_info_prompt_sections_default_on() {
+        let router = McpRouter::with_config(AppState::new(), Config::default());
+        let get = |arguments: Value| {
+            router.handle_prompts_get(mcp_spec::protocol::JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                id: Some(1),
+                method: "prompts/get".to_string(),
+                params: Some(serde_json::json!({
+                    "name": DEBUG_INFO_PROMPT,
+                    "arguments": arguments
+                })),
+            })
+        };
+
+        let response = get(serde_json::json!({})).await.unwrap();
+        let text = response.result.unwrap()["messages"][0]["content"]["text"].to_string();
+        assert!(text.contains("## Metrics"));
+        assert!(text.contains("## Recent Tool Calls"));
+
+        let response = get(serde_json::json!({ "include_metrics": "false" }))
+            .await
+            .unwrap();
+        let text = response.result.unwrap()["messages"][0]["content"]["text"].to_string();
+        assert!(!text.contains("## Metrics"));
+        assert!(text.contains("## Recent Tool Calls"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_limited_by_concurrency() {
+        let state = AppState::new().with_tool_concurrency(1, std::time::Duration::from_millis(50));
+        let router = McpRouter::with_config(state.clone(), Config::default());
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let args = serde_json::json!({ "pattern": "x", "directory": temp_dir.path() });
+
+        // A slow call holds the only slot, so an overlapping call is rejected
+        let slow_call = state.acquire_tool_permit("file_search").await.unwrap();
+        let result = router.call_tool("file_search", args.clone()).await;
+        assert!(
+            matches!(&result, Err(ToolError::ExecutionError(msg)) if msg.contains("Rate limit")),
+            "expected rejection, got {result:?}"
+        );
+        assert!(state.tool_calls.read().await.is_empty());
+
+        // A call queued while the slot is busy runs once it is released
+        let queued = tokio::spawn({
+            let router = router.clone();
+            let args = args.clone();
+            async move { router.call_tool("file_search", args).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        drop(slow_call);
+        assert!(queued.await.unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_large_results_compressed_over_threshold() {
+        let large = "match line\n".repeat(1000);
+        let content = vec![Content::text("short"), Content::text(large.clone())];
+
+        let compressed = compress_large_content(content, 1024, "call-1");
+        assert_eq!(compressed[0], Content::text("short"));
+
+        let Content::Resource(resource) = &compressed[1] else {
+            panic!("expected an embedded resource, got {:?}", compressed[1]);
+        };
+        let ResourceContents::BlobResourceContents {
+            uri,
+            mime_type,
+            blob,
+        } = &resource.resource
+        else {
+            panic!("expected a blob resource");
+        };
+        assert_eq!(uri, "tool-result://call-1/1");
+        assert_eq!(mime_type.as_deref(), Some(GZIP_MIME_TYPE));
+
+        let gzipped = base64::engine::general_purpose::STANDARD
+            .decode(blob)
+            .unwrap();
+        let mut text = String::new();
+        flate2::read::GzDecoder::new(gzipped.as_slice())
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, large);
+    }
+
+    #[test]
+    fn test_results_left_as_text_under_threshold_or_disabled() {
+        let large = "match line\n".repeat(1000);
+        let content = vec![Content::text(large.clone())];
+
+        assert_eq!(
+            compress_large_content(content.clone(), large.len() as u64, "call-1"),
+            content
+        );
+        assert_eq!(
+            compress_large_content(content.clone(), 0, "call-1"),
+            content
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_keep_session_alive() {
+        let state = AppState::new();
+        let router = McpRouter::with_config(state.clone(), Config::default());
+        let session_id = router.start_session();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let args = serde_json::json!({ "pattern": "x", "directory": temp_dir.path() });
+
+        router.call_tool("file_search", args.clone()).await.unwrap();
+        assert_eq!(
+            state
+                .active_sessions
+                .get(&session_id)
+                .unwrap()
+                .request_count,
+            1
+        );
+
+        // A call after the session expired starts a new one
+        state.active_sessions.remove(&session_id);
+        router.call_tool("file_search", args).await.unwrap();
+        assert_eq!(state.active_sessions.len(), 1);
+        assert!(!state.active_sessions.contains_key(&session_id));
+    }
+
+    #[tokio::test]
+    async fn test_tool_errors_carry_codes() {
+        let router = router(false);
+        let result = router
+            .call_tool("no_such_tool", serde_json::json!({}))
+            .await;
+        assert!(
+            matches!(&result, Err(ToolError::InvalidParameters(msg)) if msg.starts_with("[TOOL_NOT_FOUND] ")),
+            "unexpected result {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics_tool_reads_recorded_counter() {
+        let state = AppState::new();
+        state.metrics.insert(
+            "tool_calls_file_search".to_string(),
+            crate::shared::state::MetricValue::Counter(4),
+        );
+        let router = McpRouter::with_config(state, Config::default());
+        assert!(router.list_tools().iter().any(|t| t.name == "get_metrics"));
+
+        let blocks = router
+            .call_tool(
+                "get_metrics",
+                serde_json::json!({ "name": "tool_calls_file_search" }),
+            )
+            .await
+            .unwrap();
+        let output: Value = serde_json::from_str(blocks[0].as_text().unwrap()).unwrap();
+        assert_eq!(output["metrics"][0]["kind"], "counter");
+        assert_eq!(output["metrics"][0]["value"], 4.0);
     }
 }
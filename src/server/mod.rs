@@ -5,18 +5,21 @@
 
 use mcp_server::{router::RouterService, ByteTransport, Server};
 
-use crate::shared::state::AppState;
+use crate::shared::{config::Config, state::AppState};
 
+pub mod debug_info;
 pub mod error;
 pub mod mcp_router;
 
 pub use mcp_router::McpRouter;
 
-/// Creates a new MCP server instance with the given application state.
+/// Creates a new MCP server instance with the given application state and configuration.
 pub async fn create_mcp_server(
     state: AppState,
+    config: Config,
 ) -> anyhow::Result<Server<RouterService<McpRouter>>> {
-    let router = McpRouter::new(state);
+    let router = McpRouter::with_config(state, config);
+    router.start_session();
     let router_service = RouterService(router);
     let server = Server::new(router_service);
     Ok(server)
@@ -1,5 +1,8 @@
 use std::env;
+use std::path::Path;
+use std::str::FromStr;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,34 +12,187 @@ pub struct Config {
     pub rate_limiting: RateLimitingConfig,
     pub resource_limits: ResourceLimitsConfig,
     pub development: DevelopmentConfig,
+    /// Prompt templates served over MCP; only settable from a config file
+    #[serde(default)]
+    pub prompts: Vec<PromptTemplateConfig>,
+}
+
+/// A prompt template defined in the config file (`[[prompts]]`).
+///
+/// `{argument}` placeholders in `template` are filled in with the arguments
+/// the client supplies when it gets the prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptTemplateConfig {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<PromptArgumentConfig>,
+    pub template: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptArgumentConfig {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub dashboard_port: u16,
     pub dashboard_host: String,
+    /// Resolve `dashboard_host` during validation
+    pub resolve_dashboard_host: bool,
     pub log_level: String,
+    pub enable_tool_reflection: bool,
+    pub enable_tool_examples: bool,
+    pub timestamp_format: TimestampFormat,
+    pub structured_tool_content: bool,
+    /// Tool result text blocks larger than this are gzipped; 0 disables compression
+    pub tool_result_compression_threshold_bytes: u64,
+    pub tool_call_log_path: Option<String>,
+    pub tool_call_log_compression: bool,
+    pub enable_tool_health: bool,
+    pub enable_tool_usage: bool,
+    pub enable_limits_reporting: bool,
+    /// Serve the downloadable `/api/diagnostics` bundle
+    pub enable_diagnostics: bool,
+    pub redact_limits: bool,
+    pub tool_call_batching: bool,
+    pub event_log_path: Option<String>,
+    pub event_log_max_bytes: u64,
+    pub enable_config_reload: bool,
+    pub shutdown_grace_seconds: u64,
+    /// /health reports degraded once the MCP heartbeat is older than this; 0 disables
+    pub health_heartbeat_max_age_seconds: u64,
+    /// In `both` mode, keep either server running when the other one exits
+    pub independent_servers: bool,
+    /// How servers are restarted when `independent_servers` is set
+    pub server_restart_policy: RestartPolicy,
+    /// In mcp-only mode, times a failed MCP server is restarted before giving up
+    pub mcp_max_restarts: u32,
+    /// Delay before the first MCP restart, doubled for each further restart
+    pub mcp_restart_backoff_ms: u64,
+    /// MCP sessions idle for longer than this are removed; 0 keeps them forever
+    pub session_idle_timeout_seconds: u64,
+    pub event_dedup_window_ms: u64,
+    /// Window in which SSE/WebSocket frames are coalesced per event type; 0 disables
+    pub event_debounce_ms: u64,
+    /// Most events of any one type broadcast per second; 0 disables the limit
+    pub event_rate_limit_per_second: u32,
+    /// Seconds between process and host resource samples; 0 disables sampling
+    pub metrics_sample_interval_seconds: u64,
+    /// OTLP collector that tool call and HTTP spans are exported to
+    pub otlp_endpoint: Option<String>,
+    /// PEM certificate chain; with `tls_key_path` the dashboard serves HTTPS
+    pub tls_cert_path: Option<String>,
+    /// PEM private key matching `tls_cert_path`
+    pub tls_key_path: Option<String>,
+    /// TOML file the configuration was loaded from, re-read on reload
+    #[serde(skip)]
+    pub config_file: Option<String>,
+}
+
+/// How timestamps are rendered in JSON API responses.
+///
+/// HTML templates always use human-readable formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampFormat {
+    /// RFC3339 / ISO8601 string, e.g. `2024-01-01T12:00:00.000Z`
+    Rfc3339,
+    /// Milliseconds since the Unix epoch as a number
+    EpochMillis,
+}
+
+impl TimestampFormat {
+    /// Render a timestamp for a JSON response
+    pub fn format(self, timestamp: DateTime<Utc>) -> serde_json::Value {
+        match self {
+            TimestampFormat::Rfc3339 => serde_json::Value::String(
+                timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            ),
+            TimestampFormat::EpochMillis => serde_json::Value::from(timestamp.timestamp_millis()),
+        }
+    }
+}
+
+impl FromStr for TimestampFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rfc3339" | "iso8601" => Ok(TimestampFormat::Rfc3339),
+            "epoch_millis" => Ok(TimestampFormat::EpochMillis),
+            _ => Err(format!("Unknown timestamp format: {s}")),
+        }
+    }
+}
+
+/// When a server supervised in `both` mode is started again after it stops
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPolicy {
+    /// Leave it stopped
+    #[default]
+    Never,
+    /// Restart only if it exited with an error
+    OnFailure,
+    /// Restart whenever it exits
+    Always,
+}
+
+impl FromStr for RestartPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "never" => Ok(RestartPolicy::Never),
+            "on_failure" | "on-failure" => Ok(RestartPolicy::OnFailure),
+            "always" => Ok(RestartPolicy::Always),
+            _ => Err(format!("Unknown restart policy: {s}")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityConfig {
     pub max_tool_execution_time_ms: u64,
     pub max_concurrent_tool_calls: usize,
+    pub tool_queue_timeout_ms: u64,
     pub max_file_size_bytes: u64,
     pub allowed_file_extensions: Vec<String>,
     pub websocket_allowed_origins: Vec<String>,
+    pub enable_directory_streaming: bool,
+    /// Directories `/api/directory/stream` may list, including subdirectories
+    pub directory_streaming_roots: Vec<String>,
+    pub tool_failure_threshold: u32,
+    pub tool_failure_cooldown_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitingConfig {
+    pub enabled: bool,
     pub requests_per_minute: u32,
     pub burst_size: u32,
+    /// WebSocket connections accepted per client IP within the window
+    pub websocket_connections_per_ip: usize,
+    pub websocket_window_seconds: u64,
+    /// How often IPs with no recent WebSocket connections are purged
+    pub websocket_cleanup_interval_seconds: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceLimitsConfig {
     pub max_http_response_size_bytes: u64,
     pub http_timeout_seconds: u64,
+    pub tool_worker_pool_size: usize,
+    pub max_tool_call_history: usize,
+    pub event_history_size: usize,
+    pub file_search_mmap_threshold_bytes: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +200,7 @@ pub struct DevelopmentConfig {
     pub enable_cors: bool,
     pub enable_debug_routes: bool,
     pub hot_reload_debounce_ms: u64,
+    pub validate_tool_output: bool,
 }
 
 impl Default for Config {
@@ -52,11 +209,44 @@ impl Default for Config {
             server: ServerConfig {
                 dashboard_port: 8080,
                 dashboard_host: "0.0.0.0".to_string(),
+                resolve_dashboard_host: false,
                 log_level: "info".to_string(),
+                enable_tool_reflection: false,
+                enable_tool_examples: false,
+                timestamp_format: TimestampFormat::Rfc3339,
+                structured_tool_content: false,
+                tool_result_compression_threshold_bytes: 0,
+                tool_call_log_path: None,
+                tool_call_log_compression: false,
+                enable_tool_health: false,
+                enable_tool_usage: false,
+                enable_limits_reporting: false,
+                enable_diagnostics: false,
+                redact_limits: false,
+                tool_call_batching: false,
+                event_log_path: None,
+                event_log_max_bytes: 10 * 1024 * 1024, // 10MB
+                enable_config_reload: false,
+                shutdown_grace_seconds: 10,
+                health_heartbeat_max_age_seconds: 0,
+                independent_servers: false,
+                server_restart_policy: RestartPolicy::Never,
+                mcp_max_restarts: 3,
+                mcp_restart_backoff_ms: 1000,
+                session_idle_timeout_seconds: 1800,
+                event_dedup_window_ms: 0,
+                event_debounce_ms: 0,
+                event_rate_limit_per_second: 0,
+                metrics_sample_interval_seconds: 15,
+                otlp_endpoint: None,
+                tls_cert_path: None,
+                tls_key_path: None,
+                config_file: None,
             },
             security: SecurityConfig {
                 max_tool_execution_time_ms: 30000,
-                max_concurrent_tool_calls: 10,
+                max_concurrent_tool_calls: crate::shared::state::DEFAULT_MAX_CONCURRENT_TOOL_CALLS,
+                tool_queue_timeout_ms: 1000,
                 max_file_size_bytes: 10 * 1024 * 1024, // 10MB
                 allowed_file_extensions: vec!["txt", "json", "toml", "yaml", "yml", "md", "log"]
                     .into_iter()
@@ -66,27 +256,81 @@ impl Default for Config {
                     "http://localhost:8080".to_string(),
                     "http://127.0.0.1:8080".to_string(),
                 ],
+                enable_directory_streaming: false,
+                directory_streaming_roots: Vec::new(),
+                tool_failure_threshold: 0,
+                tool_failure_cooldown_ms: 30000,
             },
             rate_limiting: RateLimitingConfig {
+                enabled: false,
                 requests_per_minute: 60,
                 burst_size: 10,
+                websocket_connections_per_ip: 10,
+                websocket_window_seconds: 60,
+                websocket_cleanup_interval_seconds: 60,
             },
             resource_limits: ResourceLimitsConfig {
                 max_http_response_size_bytes: 5 * 1024 * 1024, // 5MB
                 http_timeout_seconds: 30,
+                tool_worker_pool_size: 0,
+                max_tool_call_history: crate::shared::state::DEFAULT_MAX_TOOL_CALLS,
+                event_history_size: crate::shared::state::DEFAULT_EVENT_HISTORY,
+                file_search_mmap_threshold_bytes: 8 * 1024 * 1024, // 8MB
             },
             development: DevelopmentConfig {
                 enable_cors: false,
                 enable_debug_routes: false,
                 hot_reload_debounce_ms: 500,
+                validate_tool_output: false,
             },
+            prompts: Vec::new(),
         }
     }
 }
 
 impl Config {
     pub fn from_env() -> Result<Self, crate::server::error::McpServerError> {
-        let mut config = Self::default();
+        Self::default().with_env()
+    }
+
+    /// Load configuration from a TOML file.
+    ///
+    /// The file uses the same sections as `Config` (`[server]`, `[security]`,
+    /// ...); settings it leaves out keep their defaults.
+    pub fn from_file(path: &Path) -> Result<Self, crate::server::error::McpServerError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            crate::server::error::McpServerError::Config(format!(
+                "Cannot read config file {}: {e}",
+                path.display()
+            ))
+        })?;
+        let file: toml::Value = toml::from_str(&contents).map_err(|e| {
+            crate::server::error::McpServerError::Config(format!(
+                "Invalid config file {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        let mut merged = toml::Value::try_from(Self::default()).map_err(|e| {
+            crate::server::error::McpServerError::Config(format!(
+                "Cannot serialize default config: {e}"
+            ))
+        })?;
+        merge_toml(&mut merged, file);
+
+        let mut config: Self = merged.try_into().map_err(|e| {
+            crate::server::error::McpServerError::Config(format!(
+                "Invalid config file {}: {e}",
+                path.display()
+            ))
+        })?;
+        config.server.config_file = Some(path.display().to_string());
+        Ok(config)
+    }
+
+    /// Override settings with any that are set in the environment
+    pub fn with_env(self) -> Result<Self, crate::server::error::McpServerError> {
+        let mut config = self;
 
         // Validate critical environment variables first
         Self::validate_env_vars()?;
@@ -99,13 +343,238 @@ impl Config {
         }
 
         if let Ok(host) = env::var("DASHBOARD_HOST") {
-            config.server.dashboard_host = host;
+            config.server.dashboard_host = Self::normalize_host(&host);
+        }
+
+        if let Ok(resolve) = env::var("RESOLVE_DASHBOARD_HOST") {
+            config.server.resolve_dashboard_host = resolve.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid RESOLVE_DASHBOARD_HOST".to_string(),
+                )
+            })?;
         }
 
         if let Ok(log_level) = env::var("RUST_LOG") {
             config.server.log_level = log_level;
         }
 
+        if let Ok(reflection) = env::var("ENABLE_TOOL_REFLECTION") {
+            config.server.enable_tool_reflection = reflection.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_TOOL_REFLECTION".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(examples) = env::var("ENABLE_TOOL_EXAMPLES") {
+            config.server.enable_tool_examples = examples.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_TOOL_EXAMPLES".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(health) = env::var("ENABLE_TOOL_HEALTH") {
+            config.server.enable_tool_health = health.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_TOOL_HEALTH".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(usage) = env::var("ENABLE_TOOL_USAGE") {
+            config.server.enable_tool_usage = usage.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_TOOL_USAGE".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(diagnostics) = env::var("ENABLE_DIAGNOSTICS") {
+            config.server.enable_diagnostics = diagnostics.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_DIAGNOSTICS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(limits) = env::var("ENABLE_LIMITS_REPORTING") {
+            config.server.enable_limits_reporting = limits.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_LIMITS_REPORTING".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(redact) = env::var("REDACT_LIMITS") {
+            config.server.redact_limits = redact.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config("Invalid REDACT_LIMITS".to_string())
+            })?;
+        }
+
+        if let Ok(batching) = env::var("TOOL_CALL_BATCHING") {
+            config.server.tool_call_batching = batching.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid TOOL_CALL_BATCHING".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(path) = env::var("EVENT_LOG_PATH") {
+            config.server.event_log_path = (!path.trim().is_empty()).then_some(path);
+        }
+
+        if let Ok(endpoint) = env::var("OTLP_ENDPOINT") {
+            config.server.otlp_endpoint = (!endpoint.trim().is_empty()).then_some(endpoint);
+        }
+
+        if let Ok(path) = env::var("TLS_CERT_PATH") {
+            config.server.tls_cert_path = (!path.trim().is_empty()).then_some(path);
+        }
+
+        if let Ok(path) = env::var("TLS_KEY_PATH") {
+            config.server.tls_key_path = (!path.trim().is_empty()).then_some(path);
+        }
+
+        if let Ok(max_bytes) = env::var("EVENT_LOG_MAX_BYTES") {
+            config.server.event_log_max_bytes = max_bytes.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid EVENT_LOG_MAX_BYTES".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(reload) = env::var("ENABLE_CONFIG_RELOAD") {
+            config.server.enable_config_reload = reload.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_CONFIG_RELOAD".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(independent) = env::var("INDEPENDENT_SERVERS") {
+            config.server.independent_servers = independent.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid INDEPENDENT_SERVERS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(policy) = env::var("SERVER_RESTART_POLICY") {
+            config.server.server_restart_policy = policy.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid SERVER_RESTART_POLICY".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(restarts) = env::var("MCP_MAX_RESTARTS") {
+            config.server.mcp_max_restarts = restarts.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config("Invalid MCP_MAX_RESTARTS".to_string())
+            })?;
+        }
+
+        if let Ok(backoff) = env::var("MCP_RESTART_BACKOFF_MS") {
+            config.server.mcp_restart_backoff_ms = backoff.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid MCP_RESTART_BACKOFF_MS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(timeout) = env::var("SESSION_IDLE_TIMEOUT_SECONDS") {
+            config.server.session_idle_timeout_seconds = timeout.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid SESSION_IDLE_TIMEOUT_SECONDS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(interval) = env::var("METRICS_SAMPLE_INTERVAL_SECONDS") {
+            config.server.metrics_sample_interval_seconds = interval.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid METRICS_SAMPLE_INTERVAL_SECONDS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(grace) = env::var("SHUTDOWN_GRACE_SECONDS") {
+            config.server.shutdown_grace_seconds = grace.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid SHUTDOWN_GRACE_SECONDS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(max_age) = env::var("HEALTH_HEARTBEAT_MAX_AGE_SECONDS") {
+            config.server.health_heartbeat_max_age_seconds = max_age.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid HEALTH_HEARTBEAT_MAX_AGE_SECONDS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(window) = env::var("EVENT_DEDUP_WINDOW_MS") {
+            config.server.event_dedup_window_ms = window.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid EVENT_DEDUP_WINDOW_MS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(debounce) = env::var("EVENT_DEBOUNCE_MS") {
+            config.server.event_debounce_ms = debounce.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid EVENT_DEBOUNCE_MS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(rate) = env::var("EVENT_RATE_LIMIT_PER_SECOND") {
+            config.server.event_rate_limit_per_second = rate.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid EVENT_RATE_LIMIT_PER_SECOND".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(format) = env::var("API_TIMESTAMP_FORMAT") {
+            config.server.timestamp_format = format.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid API_TIMESTAMP_FORMAT".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(structured) = env::var("STRUCTURED_TOOL_CONTENT") {
+            config.server.structured_tool_content = structured.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid STRUCTURED_TOOL_CONTENT".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(threshold) = env::var("TOOL_RESULT_COMPRESSION_THRESHOLD_BYTES") {
+            config.server.tool_result_compression_threshold_bytes =
+                threshold.parse().map_err(|_| {
+                    crate::server::error::McpServerError::Config(
+                        "Invalid TOOL_RESULT_COMPRESSION_THRESHOLD_BYTES".to_string(),
+                    )
+                })?;
+        }
+
+        if let Ok(path) = env::var("TOOL_CALL_LOG_PATH") {
+            config.server.tool_call_log_path = (!path.trim().is_empty()).then_some(path);
+        }
+
+        if let Ok(compress) = env::var("TOOL_CALL_LOG_COMPRESSION") {
+            config.server.tool_call_log_compression = compress.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid TOOL_CALL_LOG_COMPRESSION".to_string(),
+                )
+            })?;
+        }
+
         // Security configuration
         if let Ok(timeout) = env::var("MAX_TOOL_EXECUTION_TIME_MS") {
             config.security.max_tool_execution_time_ms = timeout.parse().map_err(|_| {
@@ -123,6 +592,14 @@ impl Config {
             })?;
         }
 
+        if let Ok(timeout) = env::var("TOOL_QUEUE_TIMEOUT_MS") {
+            config.security.tool_queue_timeout_ms = timeout.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid TOOL_QUEUE_TIMEOUT_MS".to_string(),
+                )
+            })?;
+        }
+
         if let Ok(max_size) = env::var("MAX_FILE_SIZE_BYTES") {
             config.security.max_file_size_bytes = max_size.parse().map_err(|_| {
                 crate::server::error::McpServerError::Config(
@@ -143,7 +620,47 @@ impl Config {
                 origins.split(',').map(|s| s.trim().to_string()).collect();
         }
 
+        if let Ok(streaming) = env::var("ENABLE_DIRECTORY_STREAMING") {
+            config.security.enable_directory_streaming = streaming.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_DIRECTORY_STREAMING".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(roots) = env::var("DIRECTORY_STREAMING_ROOTS") {
+            config.security.directory_streaming_roots = roots
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+
+        if let Ok(threshold) = env::var("TOOL_FAILURE_THRESHOLD") {
+            config.security.tool_failure_threshold = threshold.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid TOOL_FAILURE_THRESHOLD".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(cooldown) = env::var("TOOL_FAILURE_COOLDOWN_MS") {
+            config.security.tool_failure_cooldown_ms = cooldown.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid TOOL_FAILURE_COOLDOWN_MS".to_string(),
+                )
+            })?;
+        }
+
         // Rate limiting configuration
+        if let Ok(enabled) = env::var("ENABLE_RATE_LIMITING") {
+            config.rate_limiting.enabled = enabled.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid ENABLE_RATE_LIMITING".to_string(),
+                )
+            })?;
+        }
+
         if let Ok(rpm) = env::var("RATE_LIMIT_REQUESTS_PER_MINUTE") {
             config.rate_limiting.requests_per_minute = rpm.parse().map_err(|_| {
                 crate::server::error::McpServerError::Config(
@@ -160,6 +677,32 @@ impl Config {
             })?;
         }
 
+        if let Ok(connections) = env::var("WS_CONNECTIONS_PER_IP") {
+            config.rate_limiting.websocket_connections_per_ip =
+                connections.parse().map_err(|_| {
+                    crate::server::error::McpServerError::Config(
+                        "Invalid WS_CONNECTIONS_PER_IP".to_string(),
+                    )
+                })?;
+        }
+
+        if let Ok(window) = env::var("WS_CONNECTION_WINDOW_SECONDS") {
+            config.rate_limiting.websocket_window_seconds = window.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid WS_CONNECTION_WINDOW_SECONDS".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(interval) = env::var("WS_RATE_LIMIT_CLEANUP_INTERVAL_SECONDS") {
+            config.rate_limiting.websocket_cleanup_interval_seconds =
+                interval.parse().map_err(|_| {
+                    crate::server::error::McpServerError::Config(
+                        "Invalid WS_RATE_LIMIT_CLEANUP_INTERVAL_SECONDS".to_string(),
+                    )
+                })?;
+        }
+
         // Resource limits configuration
         if let Ok(max_size) = env::var("MAX_HTTP_RESPONSE_SIZE_BYTES") {
             config.resource_limits.max_http_response_size_bytes =
@@ -178,6 +721,39 @@ impl Config {
             })?;
         }
 
+        if let Ok(pool_size) = env::var("TOOL_WORKER_POOL_SIZE") {
+            config.resource_limits.tool_worker_pool_size = pool_size.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid TOOL_WORKER_POOL_SIZE".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(history) = env::var("MAX_TOOL_CALL_HISTORY") {
+            config.resource_limits.max_tool_call_history = history.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid MAX_TOOL_CALL_HISTORY".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(history) = env::var("EVENT_HISTORY_SIZE") {
+            config.resource_limits.event_history_size = history.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid EVENT_HISTORY_SIZE".to_string(),
+                )
+            })?;
+        }
+
+        if let Ok(threshold) = env::var("FILE_SEARCH_MMAP_THRESHOLD_BYTES") {
+            config.resource_limits.file_search_mmap_threshold_bytes =
+                threshold.parse().map_err(|_| {
+                    crate::server::error::McpServerError::Config(
+                        "Invalid FILE_SEARCH_MMAP_THRESHOLD_BYTES".to_string(),
+                    )
+                })?;
+        }
+
         // Development configuration
         if let Ok(cors) = env::var("ENABLE_CORS") {
             config.development.enable_cors = cors.parse().map_err(|_| {
@@ -201,6 +777,14 @@ impl Config {
             })?;
         }
 
+        if let Ok(validate) = env::var("VALIDATE_TOOL_OUTPUT") {
+            config.development.validate_tool_output = validate.parse().map_err(|_| {
+                crate::server::error::McpServerError::Config(
+                    "Invalid VALIDATE_TOOL_OUTPUT".to_string(),
+                )
+            })?;
+        }
+
         Ok(config)
     }
 
@@ -223,25 +807,87 @@ impl Config {
                 ));
             }
             // Basic IP/hostname validation
-            if !Self::is_valid_host(&host) {
+            if !Self::is_valid_host(&Self::normalize_host(&host)) {
                 return Err(crate::server::error::McpServerError::Config(format!(
                     "DASHBOARD_HOST '{host}' is not a valid host format"
                 )));
             }
         }
 
+        // Validate timestamp format if set
+        if let Ok(format) = env::var("API_TIMESTAMP_FORMAT") {
+            if format.parse::<TimestampFormat>().is_err() {
+                return Err(crate::server::error::McpServerError::Config(format!(
+                    "API_TIMESTAMP_FORMAT must be 'rfc3339' or 'epoch_millis', got '{format}'"
+                )));
+            }
+        }
+
+        if let Ok(policy) = env::var("SERVER_RESTART_POLICY") {
+            if policy.parse::<RestartPolicy>().is_err() {
+                return Err(crate::server::error::McpServerError::Config(format!(
+                    "SERVER_RESTART_POLICY must be 'never', 'on_failure' or 'always', got '{policy}'"
+                )));
+            }
+        }
+
         // Validate numeric environment variables
         Self::validate_numeric_env("MAX_TOOL_EXECUTION_TIME_MS", 1000, 600000)?;
         Self::validate_numeric_env("MAX_CONCURRENT_TOOL_CALLS", 1, 100)?;
+        Self::validate_numeric_env("TOOL_QUEUE_TIMEOUT_MS", 0, 60000)?;
         Self::validate_numeric_env("MAX_FILE_SIZE_BYTES", 1024, 100 * 1024 * 1024)?;
+        Self::validate_numeric_env("TOOL_FAILURE_THRESHOLD", 0, 1000)?;
+        Self::validate_numeric_env("TOOL_FAILURE_COOLDOWN_MS", 100, 3600000)?;
         Self::validate_numeric_env("RATE_LIMIT_REQUESTS_PER_MINUTE", 1, 10000)?;
         Self::validate_numeric_env("RATE_LIMIT_BURST_SIZE", 1, 1000)?;
+        Self::validate_numeric_env("WS_CONNECTIONS_PER_IP", 1, 1000)?;
+        Self::validate_numeric_env("WS_CONNECTION_WINDOW_SECONDS", 1, 3600)?;
+        Self::validate_numeric_env("WS_RATE_LIMIT_CLEANUP_INTERVAL_SECONDS", 1, 86400)?;
         Self::validate_numeric_env("MAX_HTTP_RESPONSE_SIZE_BYTES", 1024, 50 * 1024 * 1024)?;
         Self::validate_numeric_env("HTTP_TIMEOUT_SECONDS", 1, 300)?;
+        Self::validate_numeric_env("TOOL_WORKER_POOL_SIZE", 0, 100)?;
+        Self::validate_numeric_env("MAX_TOOL_CALL_HISTORY", 1, 100000)?;
+        Self::validate_numeric_env("EVENT_HISTORY_SIZE", 1, 100000)?;
+        Self::validate_numeric_env(
+            "TOOL_RESULT_COMPRESSION_THRESHOLD_BYTES",
+            0,
+            50 * 1024 * 1024,
+        )?;
+        Self::validate_numeric_env(
+            "FILE_SEARCH_MMAP_THRESHOLD_BYTES",
+            0,
+            64 * 1024 * 1024 * 1024,
+        )?;
+        Self::validate_numeric_env("EVENT_LOG_MAX_BYTES", 1024, 1024 * 1024 * 1024)?;
+        Self::validate_numeric_env("SHUTDOWN_GRACE_SECONDS", 0, 300)?;
+        Self::validate_numeric_env("HEALTH_HEARTBEAT_MAX_AGE_SECONDS", 0, 86400)?;
+        Self::validate_numeric_env("SESSION_IDLE_TIMEOUT_SECONDS", 0, 7 * 24 * 3600)?;
+        Self::validate_numeric_env("MCP_MAX_RESTARTS", 0, 100)?;
+        Self::validate_numeric_env("MCP_RESTART_BACKOFF_MS", 0, 60000)?;
+        Self::validate_numeric_env("EVENT_DEDUP_WINDOW_MS", 0, 60000)?;
+        Self::validate_numeric_env("EVENT_RATE_LIMIT_PER_SECOND", 0, 100000)?;
+        Self::validate_numeric_env("EVENT_DEBOUNCE_MS", 0, 60000)?;
+        Self::validate_numeric_env("METRICS_SAMPLE_INTERVAL_SECONDS", 0, 3600)?;
 
         // Validate boolean environment variables
+        Self::validate_boolean_env("RESOLVE_DASHBOARD_HOST")?;
+        Self::validate_boolean_env("INDEPENDENT_SERVERS")?;
         Self::validate_boolean_env("ENABLE_CORS")?;
+        Self::validate_boolean_env("ENABLE_RATE_LIMITING")?;
         Self::validate_boolean_env("ENABLE_DEBUG_ROUTES")?;
+        Self::validate_boolean_env("VALIDATE_TOOL_OUTPUT")?;
+        Self::validate_boolean_env("ENABLE_DIRECTORY_STREAMING")?;
+        Self::validate_boolean_env("ENABLE_TOOL_REFLECTION")?;
+        Self::validate_boolean_env("ENABLE_TOOL_EXAMPLES")?;
+        Self::validate_boolean_env("ENABLE_TOOL_HEALTH")?;
+        Self::validate_boolean_env("ENABLE_TOOL_USAGE")?;
+        Self::validate_boolean_env("ENABLE_LIMITS_REPORTING")?;
+        Self::validate_boolean_env("ENABLE_DIAGNOSTICS")?;
+        Self::validate_boolean_env("REDACT_LIMITS")?;
+        Self::validate_boolean_env("STRUCTURED_TOOL_CONTENT")?;
+        Self::validate_boolean_env("TOOL_CALL_LOG_COMPRESSION")?;
+        Self::validate_boolean_env("TOOL_CALL_BATCHING")?;
+        Self::validate_boolean_env("ENABLE_CONFIG_RELOAD")?;
 
         // Validate hot reload debounce timing
         Self::validate_numeric_env("HOT_RELOAD_DEBOUNCE_MS", 50, 5000)?;
@@ -286,8 +932,31 @@ impl Config {
         Ok(())
     }
 
-    /// Basic host validation (IP address or hostname)
+    /// Trim whitespace, drop a fully-qualified trailing dot and lowercase.
+    ///
+    /// Unix socket paths are only trimmed.
+    fn normalize_host(host: &str) -> String {
+        let host = host.trim();
+        if host.starts_with(UNIX_SOCKET_PREFIX) {
+            return host.to_string();
+        }
+        host.strip_suffix('.').unwrap_or(host).to_ascii_lowercase()
+    }
+
+    /// Socket path when `dashboard_host` is `unix:<path>`
+    pub fn dashboard_socket_path(&self) -> Option<&Path> {
+        self.server
+            .dashboard_host
+            .strip_prefix(UNIX_SOCKET_PREFIX)
+            .map(Path::new)
+    }
+
+    /// Host validation: an IP address, an RFC 1123 hostname or `unix:<path>`
     fn is_valid_host(host: &str) -> bool {
+        if let Some(path) = host.strip_prefix(UNIX_SOCKET_PREFIX) {
+            return !path.is_empty();
+        }
+
         // Allow localhost variations
         if host == "localhost" || host == "0.0.0.0" || host == "127.0.0.1" || host == "::1" {
             return true;
@@ -303,13 +972,92 @@ impl Config {
             return true;
         }
 
-        // Basic hostname validation (simplified)
-        if host.len() > 253 {
+        // Dot-separated labels of 1-63 letters, digits and inner hyphens
+        if host.is_empty() || host.len() > 253 {
             return false;
         }
 
-        host.chars()
-            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
+        host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+    }
+
+    /// Check that a hostname resolves, so a bad name fails at startup instead of at bind time
+    fn check_host_resolves(
+        host: &str,
+        port: u16,
+    ) -> Result<(), crate::server::error::McpServerError> {
+        use std::net::ToSocketAddrs;
+
+        if host.parse::<std::net::IpAddr>().is_ok() {
+            return Ok(());
+        }
+
+        match (host, port).to_socket_addrs() {
+            Ok(addrs) => {
+                let mut addrs = addrs;
+                if addrs.next().is_some() {
+                    Ok(())
+                } else {
+                    Err(crate::server::error::McpServerError::Config(format!(
+                        "DASHBOARD_HOST '{host}' did not resolve to any address"
+                    )))
+                }
+            }
+            Err(e) => Err(crate::server::error::McpServerError::Config(format!(
+                "DASHBOARD_HOST '{host}' could not be resolved: {e}"
+            ))),
+        }
+    }
+
+    /// Limits that apply to tool callers, as reported by `/api/limits`.
+    ///
+    /// With `redact_limits` set, the allowed extension list is withheld and
+    /// only whether access is restricted is reported.
+    pub fn limits_report(&self) -> serde_json::Value {
+        let extensions = &self.security.allowed_file_extensions;
+
+        serde_json::json!({
+            "max_file_size_bytes": self.security.max_file_size_bytes,
+            "max_tool_execution_time_ms": self.security.max_tool_execution_time_ms,
+            "max_concurrent_tool_calls": self.security.max_concurrent_tool_calls,
+            "max_http_response_size_bytes": self.resource_limits.max_http_response_size_bytes,
+            "http_timeout_seconds": self.resource_limits.http_timeout_seconds,
+            "restricted": !extensions.is_empty(),
+            "allowed_file_extensions": (!self.server.redact_limits).then_some(extensions),
+            "rate_limits": {
+                "requests_per_minute": self.rate_limiting.requests_per_minute,
+                "burst_size": self.rate_limiting.burst_size
+            }
+        })
+    }
+
+    /// The configuration as served by `/api/config` and the `config://server`
+    /// resource, with secrets masked.
+    ///
+    /// Any string containing the value of an environment variable whose name
+    /// ends in one of `SECRET_ENV_SUFFIXES` has that value replaced by
+    /// `[REDACTED]`. Values shorter than `MIN_SECRET_LEN` are ignored so flags
+    /// like `1` or `true` don't mask unrelated settings.
+    pub fn redacted(&self) -> serde_json::Value {
+        let secrets: Vec<String> = env::vars()
+            .filter(|(key, value)| {
+                let key = key.to_uppercase();
+                value.len() >= MIN_SECRET_LEN
+                    && SECRET_ENV_SUFFIXES
+                        .iter()
+                        .any(|suffix| key.ends_with(suffix))
+            })
+            .map(|(_, value)| value)
+            .collect();
+
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        redact_strings(&mut value, &secrets);
+        value
     }
 
     pub fn validate(&self) -> Result<(), crate::server::error::McpServerError> {
@@ -320,6 +1068,31 @@ impl Config {
             ));
         }
 
+        if !Self::is_valid_host(&self.server.dashboard_host) {
+            return Err(crate::server::error::McpServerError::Config(format!(
+                "DASHBOARD_HOST '{}' is not a valid host format",
+                self.server.dashboard_host
+            )));
+        }
+
+        if self.server.resolve_dashboard_host && self.dashboard_socket_path().is_none() {
+            Self::check_host_resolves(&self.server.dashboard_host, self.server.dashboard_port)?;
+        }
+
+        match (&self.server.tls_cert_path, &self.server.tls_key_path) {
+            (Some(_), None) => {
+                return Err(crate::server::error::McpServerError::Config(
+                    "TLS_CERT_PATH is set but TLS_KEY_PATH is missing".to_string(),
+                ));
+            }
+            (None, Some(_)) => {
+                return Err(crate::server::error::McpServerError::Config(
+                    "TLS_KEY_PATH is set but TLS_CERT_PATH is missing".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
         // Production hardening checks
         self.validate_production_hardening()?;
 
@@ -357,6 +1130,22 @@ impl Config {
             ));
         }
 
+        // Validate prompt templates
+        let mut prompt_names = std::collections::HashSet::new();
+        for prompt in &self.prompts {
+            if prompt.name.trim().is_empty() {
+                return Err(crate::server::error::McpServerError::Config(
+                    "Prompt names cannot be empty".to_string(),
+                ));
+            }
+            if !prompt_names.insert(prompt.name.as_str()) {
+                return Err(crate::server::error::McpServerError::Config(format!(
+                    "Duplicate prompt name: {}",
+                    prompt.name
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -420,3 +1209,53 @@ impl Config {
         (self.development.enable_cors && self.server.dashboard_host == "0.0.0.0")
     }
 }
+
+/// `DASHBOARD_HOST` prefix selecting a Unix domain socket instead of TCP
+const UNIX_SOCKET_PREFIX: &str = "unix:";
+
+/// Environment variable name suffixes whose values are treated as secrets
+const SECRET_ENV_SUFFIXES: &[&str] = &["_TOKEN", "_SECRET", "_PASSWORD", "_KEY"];
+
+/// Shortest environment variable value masked by `Config::redacted`
+const MIN_SECRET_LEN: usize = 4;
+
+/// Replace every occurrence of `secrets` in the strings of `value`
+fn redact_strings(value: &mut serde_json::Value, secrets: &[String]) {
+    match value {
+        serde_json::Value::String(text) => {
+            for secret in secrets {
+                if text.contains(secret.as_str()) {
+                    *text = text.replace(secret.as_str(), "[REDACTED]");
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                redact_strings(item, secrets);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                redact_strings(field, secrets);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Recursively overlay `overlay` onto `base`, replacing everything but tables
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
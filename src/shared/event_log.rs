@@ -0,0 +1,112 @@
+//! Durable JSONL sink for the system event stream.
+//!
+//! Each [`SystemEvent`] broadcast on [`AppState::event_tx`] is appended to a
+//! file as one `{"timestamp": ..., "event": ...}` line, independent of any
+//! in-memory buffering. When the file would grow past the configured size it
+//! is rotated to `<path>.1`, shifting older files up to `<path>.5`.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
+
+use crate::shared::state::{AppState, SystemEvent};
+
+/// Number of rotated files kept next to the active log
+const MAX_ROTATED_FILES: usize = 5;
+
+/// Appends system events to a rotating JSONL file
+pub struct EventLog {
+    path: PathBuf,
+    max_bytes: u64,
+    file: tokio::fs::File,
+    size: u64,
+}
+
+impl EventLog {
+    /// Open (or create) the event log at `path`, appending to existing contents
+    pub async fn open(path: impl Into<PathBuf>, max_bytes: u64) -> std::io::Result<Self> {
+        let path = path.into();
+        let file = open_append(&path).await?;
+        let size = file.metadata().await?.len();
+
+        Ok(Self {
+            path,
+            max_bytes,
+            file,
+            size,
+        })
+    }
+
+    /// Subscribe to the state's events and write them on a background task.
+    ///
+    /// Subscription happens before this returns, so no event sent afterwards
+    /// is missed. Must be called from within a Tokio runtime.
+    pub fn spawn(self, state: &AppState) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(self.run(state.event_tx.subscribe()))
+    }
+
+    async fn run(mut self, mut events: broadcast::Receiver<SystemEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Err(e) = self.write(&event).await {
+                        tracing::warn!("Failed to write event log {}: {e}", self.path.display());
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event log fell behind, {skipped} events were not recorded");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn write(&mut self, event: &SystemEvent) -> std::io::Result<()> {
+        let record = serde_json::json!({
+            "timestamp": Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "event": event,
+        });
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+
+        if self.size > 0 && self.size + line.len() as u64 > self.max_bytes {
+            self.rotate().await?;
+        }
+
+        self.file.write_all(&line).await?;
+        self.file.flush().await?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    async fn rotate(&mut self) -> std::io::Result<()> {
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            let from = rotated_path(&self.path, index);
+            if tokio::fs::try_exists(&from).await? {
+                tokio::fs::rename(&from, rotated_path(&self.path, index + 1)).await?;
+            }
+        }
+        tokio::fs::rename(&self.path, rotated_path(&self.path, 1)).await?;
+
+        self.file = open_append(&self.path).await?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Path of the `index`-th rotated file, e.g. `events.jsonl.1`
+pub fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+async fn open_append(path: &Path) -> std::io::Result<tokio::fs::File> {
+    tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+}
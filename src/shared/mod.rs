@@ -4,5 +4,13 @@
 //! and dashboard components, focusing on thread-safe state management.
 
 pub mod config;
+pub mod event_log;
+pub mod openapi;
+pub mod reload;
+pub mod resource_sampler;
+pub mod shutdown;
 pub mod state;
+pub mod supervisor;
+#[cfg(feature = "otel")]
+pub mod telemetry;
 pub mod types;
@@ -0,0 +1,262 @@
+//! OpenAPI 3.0 description of the dashboard REST API.
+//!
+//! The document is built from a table of the `/api` routes registered in
+//! `dashboard::server`; request and response bodies that have Rust types are
+//! described by schemas derived with `schemars`, so they stay in sync with the
+//! handlers.
+
+use schemars::gen::SchemaSettings;
+use serde_json::{json, Map, Value};
+
+use crate::dashboard::handlers::{ExecuteToolRequest, ExecuteToolResponse};
+
+/// A query or path parameter of an endpoint
+struct Parameter {
+    name: &'static str,
+    location: &'static str,
+    required: bool,
+    description: &'static str,
+}
+
+/// A documented route
+struct Endpoint {
+    path: &'static str,
+    method: &'static str,
+    summary: &'static str,
+    /// Media type of a successful response
+    content_type: &'static str,
+    parameters: &'static [Parameter],
+}
+
+const fn endpoint(
+    path: &'static str,
+    method: &'static str,
+    summary: &'static str,
+    content_type: &'static str,
+) -> Endpoint {
+    Endpoint {
+        path,
+        method,
+        summary,
+        content_type,
+        parameters: &[],
+    }
+}
+
+const JSON: &str = "application/json";
+const HTML: &str = "text/html";
+
+/// Every `/api` route, in the order they are registered
+const ENDPOINTS: &[Endpoint] = &[
+    endpoint("/api/status", "get", "MCP connection status fragment", HTML),
+    endpoint("/api/status.json", "get", "MCP connection status", JSON),
+    endpoint("/api/heartbeat", "get", "Latest MCP heartbeat", JSON),
+    endpoint("/api/version", "get", "Version and build metadata", JSON),
+    endpoint("/api/limits", "get", "Configured resource limits", JSON),
+    endpoint("/api/metrics", "get", "Metrics fragment", HTML),
+    endpoint("/api/tools", "get", "Registered tools", JSON),
+    endpoint("/api/tools/execute", "post", "Execute a tool", JSON),
+    endpoint(
+        "/api/tools/health",
+        "get",
+        "Last success and failure per tool",
+        JSON,
+    ),
+    endpoint("/api/tools/usage", "get", "Per-tool usage statistics", JSON),
+    Endpoint {
+        parameters: &[Parameter {
+            name: "name",
+            location: "path",
+            required: true,
+            description: "Tool name",
+        }],
+        ..endpoint(
+            "/api/tools/{name}/schema",
+            "get",
+            "Input and output schema of a tool",
+            JSON,
+        )
+    },
+    Endpoint {
+        parameters: &[
+            Parameter {
+                name: "caller",
+                location: "query",
+                required: false,
+                description: "Only include calls made by this caller",
+            },
+            Parameter {
+                name: "tag",
+                location: "query",
+                required: false,
+                description: "Only include calls with this tag",
+            },
+            Parameter {
+                name: "tool",
+                location: "query",
+                required: false,
+                description: "Only include calls to this tool",
+            },
+            Parameter {
+                name: "status",
+                location: "query",
+                required: false,
+                description: "Only include calls that ended in `success` or `error`",
+            },
+            Parameter {
+                name: "limit",
+                location: "query",
+                required: false,
+                description: "Calls per page, 1 to 200 (default 20)",
+            },
+            Parameter {
+                name: "offset",
+                location: "query",
+                required: false,
+                description: "Number of newer calls to skip",
+            },
+        ],
+        ..endpoint("/api/tool-calls", "get", "Recent tool calls fragment", HTML)
+    },
+    Endpoint {
+        parameters: &[Parameter {
+            name: "reset_metrics",
+            location: "query",
+            required: false,
+            description: "Also reset the per-tool call counters",
+        }],
+        ..endpoint(
+            "/api/tool-calls",
+            "delete",
+            "Clear the tool call history (debug routes only)",
+            JSON,
+        )
+    },
+    Endpoint {
+        parameters: &[Parameter {
+            name: "path",
+            location: "query",
+            required: true,
+            description: "Directory to list",
+        }],
+        ..endpoint(
+            "/api/directory/stream",
+            "get",
+            "Directory entries streamed as they are read",
+            "application/x-ndjson",
+        )
+    },
+    endpoint("/api/resources", "get", "Available MCP resources", JSON),
+    endpoint("/api/events", "get", "Recent system events", JSON),
+    endpoint("/api/sessions", "get", "Active MCP sessions", JSON),
+    endpoint("/api/config", "get", "Effective configuration", JSON),
+    endpoint(
+        "/api/diagnostics",
+        "get",
+        "Diagnostics bundle download",
+        JSON,
+    ),
+    endpoint("/api/openapi.json", "get", "This document", JSON),
+];
+
+/// Build the OpenAPI document for the dashboard API
+pub fn openapi_spec() -> Value {
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let execute_request = generator.subschema_for::<ExecuteToolRequest>();
+    let execute_response = generator.subschema_for::<ExecuteToolResponse>();
+
+    let mut paths = Map::new();
+    for endpoint in ENDPOINTS {
+        let parameters: Vec<Value> = endpoint
+            .parameters
+            .iter()
+            .map(|param| {
+                json!({
+                    "name": param.name,
+                    "in": param.location,
+                    "required": param.required,
+                    "description": param.description,
+                    "schema": { "type": "string" }
+                })
+            })
+            .collect();
+
+        let response_schema = if endpoint.path == "/api/tools/execute" {
+            json!(execute_response)
+        } else if endpoint.content_type == JSON {
+            json!({})
+        } else {
+            json!({ "type": "string" })
+        };
+
+        let mut operation = json!({
+            "summary": endpoint.summary,
+            "responses": {
+                "200": {
+                    "description": "Success",
+                    "content": { endpoint.content_type: { "schema": response_schema } }
+                }
+            }
+        });
+        if !parameters.is_empty() {
+            operation["parameters"] = Value::Array(parameters);
+        }
+        if endpoint.path == "/api/tools/execute" {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { JSON: { "schema": execute_request } }
+            });
+        }
+
+        let item = paths
+            .entry(endpoint.path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        item[endpoint.method] = operation;
+    }
+
+    // `take_definitions` skips the generator's visitors, which turn bool
+    // schemas (e.g. for `serde_json::Value`) into the objects OpenAPI 3.0 needs
+    let mut schemas = generator.take_definitions();
+    for visitor in generator.visitors_mut() {
+        for schema in schemas.values_mut() {
+            visitor.visit_schema(schema);
+        }
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "MCP Server Dashboard API",
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "paths": paths,
+        "components": { "schemas": schemas }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_refs_resolve() {
+        let spec = openapi_spec();
+        let execute = &spec["paths"]["/api/tools/execute"]["post"];
+
+        let reference = execute["requestBody"]["content"][JSON]["schema"]["$ref"]
+            .as_str()
+            .unwrap();
+        let name = reference.strip_prefix("#/components/schemas/").unwrap();
+        assert!(spec["components"]["schemas"][name]["properties"]["arguments"].is_object());
+
+        let reference = execute["responses"]["200"]["content"][JSON]["schema"]["$ref"]
+            .as_str()
+            .unwrap();
+        let name = reference.strip_prefix("#/components/schemas/").unwrap();
+        assert!(spec["components"]["schemas"][name]["properties"]["error_code"].is_object());
+
+        // OpenAPI 3.0 has no boolean schemas
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        assert!(schemas.values().all(Value::is_object));
+    }
+}
@@ -0,0 +1,113 @@
+//! Runtime configuration reload.
+//!
+//! The dashboard keeps a [`SharedConfig`] alongside the startup [`Config`].
+//! On SIGHUP the configuration is re-read from the config file (if one was
+//! given), `.env` and the environment. Settings that are read from the
+//! shared config on every use (directory streaming, WebSocket origins and the
+//! HTTP rate limit) are swapped in atomically. Every other setting is captured
+//! at startup, keeps its current value and is logged as requiring a restart.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use crate::server::error::McpServerError;
+use crate::shared::config::Config;
+
+/// Configuration that can be replaced while the server is running
+pub type SharedConfig = Arc<ArcSwap<Config>>;
+
+/// Wrap a configuration so it can be reloaded
+pub fn shared(config: Config) -> SharedConfig {
+    Arc::new(ArcSwap::from_pointee(config))
+}
+
+/// Swap the reloadable parts of `new` into `shared`.
+///
+/// Returns the changed settings, as `section.field`, that were not applied
+/// because they only take effect after a restart.
+pub fn apply_reload(shared: &SharedConfig, new: Config) -> Vec<String> {
+    let current = shared.load_full();
+
+    // Whatever `new` changes besides the live settings needs a restart
+    let mut startup_only = new.clone();
+    copy_live_settings(&mut startup_only, &current);
+    let restart_required = changed_settings(&current, &startup_only);
+
+    let mut reloaded = current.as_ref().clone();
+    copy_live_settings(&mut reloaded, &new);
+    shared.store(Arc::new(reloaded));
+    restart_required
+}
+
+/// Copy the settings that are read from the shared config on every use
+fn copy_live_settings(target: &mut Config, source: &Config) {
+    target.security.enable_directory_streaming = source.security.enable_directory_streaming;
+    target.security.directory_streaming_roots = source.security.directory_streaming_roots.clone();
+    target.security.websocket_allowed_origins = source.security.websocket_allowed_origins.clone();
+    target.rate_limiting.enabled = source.rate_limiting.enabled;
+    target.rate_limiting.requests_per_minute = source.rate_limiting.requests_per_minute;
+    target.rate_limiting.burst_size = source.rate_limiting.burst_size;
+}
+
+/// Names of the settings that differ between `current` and `new`
+fn changed_settings(current: &Config, new: &Config) -> Vec<String> {
+    use serde_json::Value;
+
+    let (Ok(Value::Object(current)), Ok(Value::Object(new))) =
+        (serde_json::to_value(current), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+
+    let mut changed = Vec::new();
+    for (section, value) in &new {
+        match (current.get(section), value) {
+            (Some(Value::Object(old_fields)), Value::Object(new_fields)) => {
+                for (field, new_value) in new_fields {
+                    if old_fields.get(field) != Some(new_value) {
+                        changed.push(format!("{section}.{field}"));
+                    }
+                }
+            }
+            (old, _) if old != Some(value) => changed.push(section.clone()),
+            _ => {}
+        }
+    }
+    changed
+}
+
+/// Re-read the config file (if any), `.env` and the environment into a
+/// validated configuration
+pub fn load(config_file: Option<&Path>) -> Result<Config, McpServerError> {
+    dotenvy::dotenv_override().ok();
+    let config = match config_file {
+        Some(path) => Config::from_file(path)?.with_env()?,
+        None => Config::from_env()?,
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+/// Reload the configuration every time the process receives SIGHUP
+#[cfg(unix)]
+pub async fn reload_on_sighup(shared: SharedConfig) -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = signal(SignalKind::hangup())?;
+    while hangups.recv().await.is_some() {
+        let config_file = shared.load().server.config_file.clone();
+        match load(config_file.as_deref().map(Path::new)) {
+            Ok(new) => {
+                let restart_required = apply_reload(&shared, new);
+                tracing::info!("🔄 Configuration reloaded");
+                for setting in restart_required {
+                    tracing::warn!("Change to {setting} requires a restart to take effect");
+                }
+            }
+            Err(e) => tracing::error!("Configuration reload failed, keeping current config: {e}"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Periodic sampling of process and host resource usage into metrics.
+//!
+//! Each sample writes `MetricValue::Gauge` entries into `AppState.metrics`,
+//! so they show up on the dashboard and the Prometheus endpoint alongside the
+//! tool call counters.
+
+use std::time::Duration;
+
+use sysinfo::{Disks, Pid, ProcessesToUpdate, System};
+
+use crate::shared::state::{AppState, MetricValue};
+
+/// Reads CPU, memory and disk usage for this process and the host
+pub struct ResourceSampler {
+    system: System,
+    disks: Disks,
+    pid: Option<Pid>,
+}
+
+impl Default for ResourceSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceSampler {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            disks: Disks::new_with_refreshed_list(),
+            pid: sysinfo::get_current_pid().ok(),
+        }
+    }
+
+    /// Refresh usage and store it as gauges.
+    ///
+    /// CPU percentages cover the time since the previous sample, so the
+    /// first sample reports 0.
+    pub fn sample(&mut self, state: &AppState) {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.disks.refresh();
+
+        let disk_total: u64 = self.disks.list().iter().map(|d| d.total_space()).sum();
+        let disk_available: u64 = self.disks.list().iter().map(|d| d.available_space()).sum();
+
+        let mut gauges = vec![
+            ("host_cpu_percent", self.system.global_cpu_usage() as f64),
+            ("host_memory_used_bytes", self.system.used_memory() as f64),
+            ("host_memory_total_bytes", self.system.total_memory() as f64),
+            (
+                "host_disk_used_bytes",
+                disk_total.saturating_sub(disk_available) as f64,
+            ),
+            ("host_disk_total_bytes", disk_total as f64),
+        ];
+
+        if let Some(pid) = self.pid {
+            self.system
+                .refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+            if let Some(process) = self.system.process(pid) {
+                gauges.push(("process_memory_bytes", process.memory() as f64));
+                gauges.push(("process_cpu_percent", process.cpu_usage() as f64));
+            }
+        }
+
+        for (name, value) in gauges {
+            state
+                .metrics
+                .insert(name.to_string(), MetricValue::Gauge(value));
+        }
+    }
+
+    /// Sample every `interval` until the returned task is aborted
+    pub fn spawn(mut self, state: &AppState, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.sample(&state);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_writes_gauges() {
+        let state = AppState::new();
+        ResourceSampler::new().sample(&state);
+
+        for name in [
+            "host_cpu_percent",
+            "host_memory_used_bytes",
+            "host_memory_total_bytes",
+            "host_disk_used_bytes",
+            "host_disk_total_bytes",
+            "process_memory_bytes",
+            "process_cpu_percent",
+        ] {
+            assert!(
+                matches!(
+                    state.metrics.get(name).as_deref(),
+                    Some(MetricValue::Gauge(_))
+                ),
+                "missing gauge {name}"
+            );
+        }
+        assert!(matches!(
+            state.metrics.get("process_memory_bytes").as_deref(),
+            Some(MetricValue::Gauge(bytes)) if *bytes > 0.0
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+//! Graceful shutdown coordination.
+//!
+//! Tool executions register with the [`ShutdownCoordinator`] held in
+//! `AppState`. Once shutdown begins no new calls are admitted, and
+//! [`ShutdownCoordinator::shutdown`] waits for the ones already running to
+//! finish, up to a grace period.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+#[derive(Default)]
+struct Inner {
+    shutting_down: AtomicBool,
+    in_flight: AtomicUsize,
+    drained: Notify,
+}
+
+/// Tracks in-flight tool calls and refuses new ones during shutdown
+#[derive(Clone, Default)]
+pub struct ShutdownCoordinator {
+    inner: Arc<Inner>,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a call, or return `None` if shutdown has begun.
+    ///
+    /// The call counts as in flight until the returned guard is dropped.
+    pub fn begin_call(&self) -> Option<InFlightCall> {
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+        if self.inner.shutting_down.load(Ordering::SeqCst) {
+            self.finish_call();
+            return None;
+        }
+        Some(InFlightCall {
+            coordinator: self.clone(),
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn is_shutting_down(&self) -> bool {
+        self.inner.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Number of calls currently executing
+    pub fn in_flight(&self) -> usize {
+        self.inner.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Stop admitting calls and wait up to `grace` for running ones to finish.
+    ///
+    /// Returns true if every in-flight call completed within the grace period.
+    pub async fn shutdown(&self, grace: Duration) -> bool {
+        self.inner.shutting_down.store(true, Ordering::SeqCst);
+
+        tokio::time::timeout(grace, async {
+            loop {
+                let drained = self.inner.drained.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                drained.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    fn finish_call(&self) {
+        if self.inner.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.drained.notify_waiters();
+        }
+    }
+}
+
+/// Marks a tool call as in flight until dropped
+pub struct InFlightCall {
+    coordinator: ShutdownCoordinator,
+}
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        self.coordinator.finish_call();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_call() {
+        let coordinator = ShutdownCoordinator::new();
+        let completed = Arc::new(AtomicBool::new(false));
+
+        // A long-running call already in progress when shutdown starts
+        let guard = coordinator.begin_call().unwrap();
+        let call = tokio::spawn({
+            let completed = completed.clone();
+            async move {
+                let _guard = guard;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                completed.store(true, Ordering::SeqCst);
+            }
+        });
+
+        assert!(coordinator.shutdown(Duration::from_secs(5)).await);
+        assert!(completed.load(Ordering::SeqCst));
+        assert_eq!(coordinator.in_flight(), 0);
+        call.await.unwrap();
+
+        // New calls are refused once shutdown has begun
+        assert!(coordinator.begin_call().is_none());
+        assert_eq!(coordinator.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_gives_up_after_grace_period() {
+        let coordinator = ShutdownCoordinator::new();
+        let _stuck = coordinator.begin_call().unwrap();
+
+        assert!(!coordinator.shutdown(Duration::from_millis(50)).await);
+        assert!(coordinator.is_shutting_down());
+        assert_eq!(coordinator.in_flight(), 1);
+    }
+}
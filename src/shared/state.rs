@@ -1,19 +1,53 @@
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use arc_swap::ArcSwap;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{broadcast, RwLock};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, mpsc, OwnedSemaphorePermit, RwLock, Semaphore};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::server::error::McpServerError;
+use crate::shared::config::TimestampFormat;
+use crate::shared::shutdown::ShutdownCoordinator;
+
+/// How often the MCP heartbeat is stamped while the server is connected
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A heartbeat older than this means the MCP server has stopped responding
+pub const HEARTBEAT_STALE_AFTER: Duration = Duration::from_secs(15);
+
+/// Most recent samples kept per histogram by `record_histogram`
+pub const MAX_HISTOGRAM_SAMPLES: usize = 1024;
+
+/// Upper bounds of the buckets histograms are expanded into for Prometheus
+const PROMETHEUS_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Default number of tool calls kept in memory (and replayed from the log)
+pub const DEFAULT_MAX_TOOL_CALLS: usize = 1000;
+
+/// Default number of broadcast events kept for `get_events`
+pub const DEFAULT_EVENT_HISTORY: usize = 500;
+
+/// Default number of tools allowed to run at the same time
+pub const DEFAULT_MAX_CONCURRENT_TOOL_CALLS: usize = 10;
+
+/// Default time a tool call waits for a free slot before it is rejected
+pub const DEFAULT_TOOL_QUEUE_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// Core application state shared between MCP server and dashboard.
 ///
 /// Uses concurrent data structures optimized for different access patterns:
 /// - `ArcSwap` for frequently-read, rarely-updated data
 /// - `DashMap` for concurrent session and metrics management  
-/// - `RwLock` for append-heavy tool call history
+/// - `RwLock` for append-heavy tool call history (a bounded ring buffer)
 /// - `broadcast::Sender` for real-time event distribution
 #[derive(Clone)]
 pub struct AppState {
@@ -25,13 +59,41 @@ pub struct AppState {
     pub event_tx: broadcast::Sender<SystemEvent>,
     /// System metrics collection
     pub metrics: Arc<DashMap<String, MetricValue>>,
-    /// Tool call execution history
-    pub tool_calls: Arc<RwLock<Vec<ToolCall>>>,
+    /// Metrics keyed by tool, metric name and labels
+    pub labeled_metrics: Arc<DashMap<MetricKey, MetricValue>>,
+    /// Recent broadcast events, oldest first (filled by `with_event_history`)
+    pub events: Arc<RwLock<VecDeque<RecordedEvent>>>,
+    /// Tracks in-flight tool calls for graceful shutdown
+    pub shutdown: ShutdownCoordinator,
+    /// Limits how many tools run at the same time
+    tool_permits: Arc<Semaphore>,
+    /// How long a tool call waits for a permit before it is rejected
+    tool_queue_timeout: Duration,
+    /// Last success and failure times per tool
+    pub tool_health: Arc<DashMap<String, ToolHealth>>,
+    /// Tool call execution history, oldest first
+    pub tool_calls: Arc<RwLock<VecDeque<ToolCall>>>,
+    /// Maximum number of tool calls kept in `tool_calls`
+    max_tool_calls: usize,
+    /// Background writer appending tool calls to the JSONL log, if enabled
+    tool_call_log: Option<mpsc::UnboundedSender<ToolCall>>,
+    /// Queue flushed into `tool_calls` in batches, if batching is enabled
+    tool_call_batch: Option<mpsc::UnboundedSender<ToolCall>>,
+    /// Suppresses consecutive duplicate events, if enabled
+    event_dedup: Option<Arc<EventDedup>>,
+    /// Caps how often each event type is broadcast, if enabled
+    event_throttle: Option<Arc<EventThrottle>>,
 }
 
 impl AppState {
     /// Create new application state with default values
     pub fn new() -> Self {
+        Self::with_history_limit(DEFAULT_MAX_TOOL_CALLS)
+    }
+
+    /// Create application state keeping at most `max_tool_calls` calls in history
+    pub fn with_history_limit(max_tool_calls: usize) -> Self {
+        let max_tool_calls = max_tool_calls.max(1);
         let (event_tx, _) = broadcast::channel(1000);
 
         Self {
@@ -39,39 +101,196 @@ impl AppState {
             active_sessions: Arc::new(DashMap::new()),
             event_tx,
             metrics: Arc::new(DashMap::new()),
-            tool_calls: Arc::new(RwLock::new(Vec::new())),
+            labeled_metrics: Arc::new(DashMap::new()),
+            events: Arc::new(RwLock::new(VecDeque::new())),
+            shutdown: ShutdownCoordinator::new(),
+            tool_permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_TOOL_CALLS)),
+            tool_queue_timeout: DEFAULT_TOOL_QUEUE_TIMEOUT,
+            tool_health: Arc::new(DashMap::new()),
+            tool_calls: Arc::new(RwLock::new(VecDeque::with_capacity(max_tool_calls))),
+            max_tool_calls,
+            tool_call_log: None,
+            tool_call_batch: None,
+            event_dedup: None,
+            event_throttle: None,
         }
     }
 
-    /// Records a tool call execution and emits a real-time event.
+    /// Create state that persists tool calls to an append-only JSONL file.
     ///
-    /// Maintains a bounded history of tool calls (max 1000 entries) and
-    /// updates relevant metrics counters.
-    pub async fn record_tool_call(
+    /// The most recent entries in the file are replayed into the in-memory
+    /// history; corrupt or partially written lines are skipped. Writes happen
+    /// on a background task so recording a call never waits on disk IO. Must
+    /// be called from within a Tokio runtime.
+    #[allow(dead_code)]
+    pub async fn with_persistence(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::with_tool_call_log(path, false, DEFAULT_MAX_TOOL_CALLS).await
+    }
+
+    /// Like [`AppState::with_persistence`], but the JSONL records are written as
+    /// a sequence of gzip segments and decompressed transparently on load.
+    ///
+    /// Corrupt segments are skipped on load; a broken trailing segment is cut
+    /// off so new segments are appended after the last readable one.
+    #[allow(dead_code)]
+    pub async fn with_compressed_persistence(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::with_tool_call_log(path, true, DEFAULT_MAX_TOOL_CALLS).await
+    }
+
+    /// Create persistent state with explicit compression and history size
+    pub async fn with_tool_call_log(
+        path: impl AsRef<Path>,
+        compress: bool,
+        max_tool_calls: usize,
+    ) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let mut state = Self::with_history_limit(max_tool_calls);
+        let (history, repair) = load_tool_call_log(path, compress, state.max_tool_calls).await?;
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        match repair {
+            LogRepair::None => {}
+            // Terminate a partial trailing line so the next record starts cleanly
+            LogRepair::TerminateLine => file.write_all(b"\n").await?,
+            // Appended segments must not follow a broken one
+            LogRepair::TruncateTo(len) => file.set_len(len).await?,
+        }
+
+        let (log_tx, log_rx) = mpsc::unbounded_channel();
+        tokio::spawn(write_tool_call_log(
+            file,
+            log_rx,
+            path.to_path_buf(),
+            compress,
+        ));
+
+        state.tool_calls = Arc::new(RwLock::new(history));
+        state.tool_call_log = Some(log_tx);
+        Ok(state)
+    }
+
+    /// Buffer recorded calls and append them to the history in batches.
+    ///
+    /// Recording then never waits on the history write lock; calls show up
+    /// in `tool_calls` shortly after `record_tool_call` returns, in order and
+    /// within the history limit. Must be called from within a Tokio runtime.
+    pub fn with_tool_call_batching(mut self) -> Self {
+        let (batch_tx, batch_rx) = mpsc::unbounded_channel();
+        tokio::spawn(flush_tool_call_batches(
+            self.tool_calls.clone(),
+            batch_rx,
+            self.max_tool_calls,
+        ));
+        self.tool_call_batch = Some(batch_tx);
+        self
+    }
+
+    /// Keep the last `capacity` broadcast events for `get_events`.
+    ///
+    /// A background task subscribes to `event_tx`, so only events sent after
+    /// this call are recorded. Must be called from within a Tokio runtime.
+    pub fn with_event_history(self, capacity: usize) -> Self {
+        tokio::spawn(record_events(
+            self.events.clone(),
+            self.event_tx.subscribe(),
+            capacity.max(1),
+        ));
+        self
+    }
+
+    /// Allow at most `max_concurrent` tools to run at once.
+    ///
+    /// A call that cannot start within `queue_timeout` is rejected so the
+    /// client can back off.
+    pub fn with_tool_concurrency(mut self, max_concurrent: usize, queue_timeout: Duration) -> Self {
+        self.tool_permits = Arc::new(Semaphore::new(max_concurrent.max(1)));
+        self.tool_queue_timeout = queue_timeout;
+        self
+    }
+
+    /// Wait for a slot to run `tool`; the slot is released when the permit drops
+    pub async fn acquire_tool_permit(
         &self,
-        call: ToolCall,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Add to history with cleanup to prevent unbounded growth
+        tool: &str,
+    ) -> Result<OwnedSemaphorePermit, McpServerError> {
+        match tokio::time::timeout(
+            self.tool_queue_timeout,
+            self.tool_permits.clone().acquire_owned(),
+        )
+        .await
         {
-            let mut tool_calls = self.tool_calls.write().await;
-            tool_calls.push(call.clone());
-
-            // Keep only the last 1000 tool calls to prevent memory issues
-            const MAX_TOOL_CALLS: usize = 1000;
-            if tool_calls.len() > MAX_TOOL_CALLS {
-                let excess = tool_calls.len() - MAX_TOOL_CALLS;
-                tool_calls.drain(0..excess);
-            }
+            Ok(Ok(permit)) => Ok(permit),
+            _ => Err(McpServerError::RateLimit {
+                endpoint: format!("tool {tool}"),
+            }),
         }
+    }
 
-        // Emit event (ignore if no subscribers)
-        let _ = self.event_tx.send(SystemEvent::ToolCalled {
-            name: call.name.clone(),
-            id: call.id,
+    /// Drop events identical to the previous one broadcast within `window`.
+    ///
+    /// Only applies to events sent through [`AppState::broadcast`]; a zero
+    /// window leaves deduplication disabled.
+    pub fn with_event_dedup(mut self, window: Duration) -> Self {
+        self.event_dedup = (!window.is_zero()).then(|| {
+            Arc::new(EventDedup {
+                window,
+                last: Mutex::new(None),
+            })
         });
+        self
+    }
+
+    /// Broadcast at most `per_second` events of each type per second.
+    ///
+    /// Events over the limit are dropped before they reach subscribers, so a
+    /// chatty tool cannot flood dashboard clients. Each type may burst up to
+    /// `per_second` events; zero leaves throttling disabled.
+    pub fn with_event_rate_limit(mut self, per_second: u32) -> Self {
+        self.event_throttle = (per_second > 0).then(|| {
+            Arc::new(EventThrottle {
+                per_second: f64::from(per_second),
+                buckets: Mutex::new(HashMap::new()),
+            })
+        });
+        self
+    }
+
+    /// Broadcast an event to subscribers (ignored if there are none).
+    ///
+    /// Returns false if the event was suppressed as a duplicate or because
+    /// its type is over the rate limit.
+    pub fn broadcast(&self, event: SystemEvent) -> bool {
+        if let Some(dedup) = &self.event_dedup {
+            if dedup.is_duplicate(&event) {
+                return false;
+            }
+        }
+        if let Some(throttle) = &self.event_throttle {
+            if !throttle.allow(event.event_type()) {
+                return false;
+            }
+        }
+        let _ = self.event_tx.send(event);
+        true
+    }
+
+    /// Records a tool call execution and emits a real-time event.
+    ///
+    /// Maintains a bounded history of tool calls (1000 entries by default),
+    /// evicting the oldest, and updates relevant metrics counters.
+    pub async fn record_tool_call(
+        &self,
+        call: ToolCall,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let name = call.name.clone();
+        self.store_tool_call(call).await;
 
         // Update metrics
-        let metric_key = format!("tool_calls_{}", call.name);
+        let metric_key = format!("tool_calls_{name}");
         let counter = self
             .metrics
             .get(&metric_key)
@@ -84,21 +303,202 @@ impl AppState {
         self.metrics
             .insert(metric_key, MetricValue::Counter(counter + 1));
 
+        self.increment_counter(MetricKey::for_tool(&name, "tool_calls"));
+
+        Ok(())
+    }
+
+    /// Empty the tool call history, returning how many calls were removed.
+    ///
+    /// With `reset_metrics` the per-tool call counters are reset as well.
+    /// Calls still queued for a batched flush are added after the clear.
+    pub async fn clear_tool_calls(&self, reset_metrics: bool) -> usize {
+        let mut tool_calls = self.tool_calls.write().await;
+        let cleared = tool_calls.len();
+        tool_calls.clear();
+
+        if reset_metrics {
+            self.metrics.retain(|key, _| {
+                !key.starts_with("tool_calls_") && !key.starts_with("tool_duration_seconds_")
+            });
+            self.labeled_metrics.retain(|key, _| key.tool.is_none());
+        }
+        cleared
+    }
+
+    /// Replace a call recorded by `record_tool_call` with its completed form.
+    ///
+    /// The history entry with the same id is updated in place and the call is
+    /// not counted again in the metrics.
+    pub async fn complete_tool_call(
+        &self,
+        call: ToolCall,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.store_tool_call(call).await;
         Ok(())
     }
 
+    /// Add or update a call in the history, then persist and announce it
+    async fn store_tool_call(&self, call: ToolCall) {
+        // Add to history with cleanup to prevent unbounded growth
+        match &self.tool_call_batch {
+            Some(batch) => {
+                let _ = batch.send(call.clone());
+            }
+            None => {
+                let mut tool_calls = self.tool_calls.write().await;
+                push_bounded(&mut tool_calls, call.clone(), self.max_tool_calls);
+            }
+        }
+
+        // Completed calls update the tool's health timestamps and durations
+        if call.result.is_some() {
+            self.record_histogram(
+                &format!("tool_duration_seconds_{}", call.name),
+                call.duration_ms.unwrap_or(0) as f64 / 1000.0,
+            );
+
+            let finished_at = call.timestamp
+                + chrono::Duration::milliseconds(call.duration_ms.unwrap_or(0) as i64);
+            let mut health = self.tool_health.entry(call.name.clone()).or_default();
+            if call.success {
+                health.last_success = Some(finished_at);
+            } else {
+                health.last_error = Some(finished_at);
+                health.last_error_message = call.error.clone();
+            }
+        }
+
+        // Persist without blocking the caller
+        if let Some(log) = &self.tool_call_log {
+            let _ = log.send(call.clone());
+        }
+
+        // Emit event (ignore if no subscribers)
+        self.broadcast(SystemEvent::ToolCalled {
+            name: call.name,
+            id: call.id,
+        });
+    }
+
+    /// Increment a structured counter metric, creating it if missing
+    pub fn increment_counter(&self, key: MetricKey) {
+        self.labeled_metrics
+            .entry(key)
+            .and_modify(|value| match value {
+                MetricValue::Counter(c) => *c += 1,
+                other => *other = MetricValue::Counter(1),
+            })
+            .or_insert(MetricValue::Counter(1));
+    }
+
+    /// Get all structured metrics recorded for a tool
+    #[allow(dead_code)]
+    pub fn tool_metrics(&self, tool: &str) -> Vec<(MetricKey, MetricValue)> {
+        let mut metrics: Vec<_> = self
+            .labeled_metrics
+            .iter()
+            .filter(|entry| entry.key().tool.as_deref() == Some(tool))
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        metrics.sort_by(|a, b| a.0.cmp(&b.0));
+        metrics
+    }
+
+    /// Render `metrics` in the Prometheus text exposition format (version 0.0.4).
+    ///
+    /// Histograms are expanded into cumulative `_bucket` lines plus `_sum` and
+    /// `_count`. Structured counters and gauges follow with their `tool` and
+    /// extra labels. Metric names are sanitized to the Prometheus character set.
+    pub fn export_prometheus(&self) -> String {
+        let mut metrics: Vec<(String, MetricValue)> = self
+            .metrics
+            .iter()
+            .map(|entry| (prometheus_name(entry.key()), entry.value().clone()))
+            .collect();
+        metrics.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut out = String::new();
+        for (name, value) in metrics {
+            match value {
+                MetricValue::Counter(c) => {
+                    out.push_str(&format!("# TYPE {name} counter\n{name} {c}\n"));
+                }
+                MetricValue::Gauge(g) => {
+                    out.push_str(&format!("# TYPE {name} gauge\n{name} {g}\n"));
+                }
+                MetricValue::Histogram(samples) => {
+                    out.push_str(&format!("# TYPE {name} histogram\n"));
+                    for bound in PROMETHEUS_BUCKETS {
+                        let count = samples.iter().filter(|v| **v <= bound).count();
+                        out.push_str(&format!("{name}_bucket{{le=\"{bound}\"}} {count}\n"));
+                    }
+                    out.push_str(&format!("{name}_bucket{{le=\"+Inf\"}} {}\n", samples.len()));
+                    out.push_str(&format!("{name}_sum {}\n", samples.iter().sum::<f64>()));
+                    out.push_str(&format!("{name}_count {}\n", samples.len()));
+                }
+            }
+        }
+
+        let mut labeled: Vec<(MetricKey, MetricValue)> = self
+            .labeled_metrics
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        labeled.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut last_name = None;
+        for (key, value) in labeled {
+            // Labeled histograms are not recorded
+            let kind = match value {
+                MetricValue::Counter(_) => "counter",
+                MetricValue::Gauge(_) => "gauge",
+                MetricValue::Histogram(_) => continue,
+            };
+            let name = prometheus_name(&key.name);
+            if last_name.as_ref() != Some(&name) {
+                out.push_str(&format!("# TYPE {name} {kind}\n"));
+                last_name = Some(name);
+            }
+            out.push_str(&format!("{} {}\n", key.labeled_name(), value.as_number()));
+        }
+        out
+    }
+
     /// Add a tool call to the history (for testing compatibility)
     #[allow(dead_code)]
     pub async fn add_tool_call(&self, call: ToolCall) {
-        self.tool_calls.write().await.push(call);
+        let mut tool_calls = self.tool_calls.write().await;
+        push_bounded(&mut tool_calls, call, self.max_tool_calls);
     }
 
-    /// Update a metric
+    /// Update a metric, replacing any previous value.
+    ///
+    /// Use `record_histogram` to add a sample to a histogram.
     #[allow(dead_code)]
     pub fn update_metric(&self, key: &str, value: MetricValue) {
         self.metrics.insert(key.to_string(), value);
     }
 
+    /// Append a sample to a histogram, keeping the last `MAX_HISTOGRAM_SAMPLES`.
+    ///
+    /// A non-histogram metric stored under `key` is replaced.
+    pub fn record_histogram(&self, key: &str, value: f64) {
+        let mut metric = self
+            .metrics
+            .entry(key.to_string())
+            .or_insert_with(|| MetricValue::Histogram(Vec::new()));
+        match metric.value_mut() {
+            MetricValue::Histogram(samples) => {
+                if samples.len() >= MAX_HISTOGRAM_SAMPLES {
+                    samples.drain(..=samples.len() - MAX_HISTOGRAM_SAMPLES);
+                }
+                samples.push(value);
+            }
+            other => *other = MetricValue::Histogram(vec![value]),
+        }
+    }
+
     /// Get all metrics (for testing)
     #[allow(dead_code)]
     pub async fn get_metrics(&self) -> std::collections::HashMap<String, MetricValue> {
@@ -169,11 +569,132 @@ impl AppState {
             .collect()
     }
 
-    /// Get events (mock implementation for compatibility)
-    #[allow(dead_code)]
-    pub fn get_events(&self, _limit: usize) -> Vec<SystemEvent> {
-        // In a real implementation, this would return stored events
-        vec![]
+    /// Mark the MCP server connected or disconnected and announce the change
+    pub fn set_mcp_connected(&self, connected: bool) {
+        self.mcp_status.rcu(|status| McpStatus {
+            connected,
+            ..(**status).clone()
+        });
+        self.broadcast(if connected {
+            SystemEvent::McpConnected
+        } else {
+            SystemEvent::McpDisconnected
+        });
+    }
+
+    /// Stamp `last_heartbeat` every `interval` while the MCP server is
+    /// connected, until the returned task is aborted
+    pub fn spawn_heartbeat(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let state = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                state.mcp_status.rcu(|status| {
+                    let mut status = (**status).clone();
+                    if status.connected {
+                        status.last_heartbeat = Some(Utc::now());
+                    }
+                    status
+                });
+            }
+        })
+    }
+
+    /// Register a new client session and announce it
+    pub fn start_session(&self) -> Uuid {
+        let session = SessionInfo::new();
+        let id = session.id;
+        self.active_sessions.insert(id, session);
+        self.broadcast(SystemEvent::SessionStarted { id });
+        id
+    }
+
+    /// Record activity on a session; false if it no longer exists
+    pub fn touch_session(&self, id: Uuid) -> bool {
+        match self.active_sessions.get_mut(&id) {
+            Some(mut session) => {
+                session.update_activity();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove sessions idle for longer than `idle_timeout`, announcing each
+    pub fn expire_idle_sessions(&self, idle_timeout: Duration) -> Vec<Uuid> {
+        let Some(cutoff) = chrono::Duration::from_std(idle_timeout)
+            .ok()
+            .and_then(|idle| Utc::now().checked_sub_signed(idle))
+        else {
+            return Vec::new();
+        };
+        let idle: Vec<Uuid> = self
+            .active_sessions
+            .iter()
+            .filter(|entry| entry.last_activity < cutoff)
+            .map(|entry| *entry.key())
+            .collect();
+
+        // Activity may have arrived since the scan, so re-check on removal
+        let expired: Vec<Uuid> = idle
+            .into_iter()
+            .filter(|id| {
+                self.active_sessions
+                    .remove_if(id, |_, session| session.last_activity < cutoff)
+                    .is_some()
+            })
+            .collect();
+        for id in &expired {
+            self.broadcast(SystemEvent::SessionExpired { id: *id });
+        }
+        expired
+    }
+
+    /// Periodically expire idle sessions until the returned task is aborted
+    pub fn spawn_session_reaper(&self, idle_timeout: Duration) -> tokio::task::JoinHandle<()> {
+        let state = self.clone();
+        let period = (idle_timeout / 2).clamp(Duration::from_secs(1), Duration::from_secs(60));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                state.expire_idle_sessions(idle_timeout);
+            }
+        })
+    }
+
+    /// Get the most recent events, newest first
+    pub async fn get_events(&self, limit: usize) -> Vec<RecordedEvent> {
+        let events = self.events.read().await;
+        events.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Per-tool usage over the completed calls in history, most used first
+    pub async fn tool_usage(&self) -> Vec<ToolUsage> {
+        let mut totals: BTreeMap<String, (usize, usize, u64)> = BTreeMap::new();
+        for call in self.tool_calls.read().await.iter() {
+            if call.result.is_none() {
+                continue;
+            }
+            let (calls, successes, duration) = totals.entry(call.name.clone()).or_default();
+            *calls += 1;
+            *successes += usize::from(call.success);
+            *duration += call.duration_ms.unwrap_or(0);
+        }
+
+        let mut usage: Vec<ToolUsage> = totals
+            .into_iter()
+            .map(|(name, (calls, successes, duration))| ToolUsage {
+                name,
+                calls,
+                success_rate: (successes as f64 / calls as f64 * 1000.0).round() / 10.0,
+                avg_duration_ms: duration as f64 / calls as f64,
+            })
+            .collect();
+        // Stable sort keeps ties in name order
+        usage.sort_by_key(|u| std::cmp::Reverse(u.calls));
+        usage
     }
 
     /// Get tool calls with limit
@@ -182,6 +703,375 @@ impl AppState {
         let calls = self.tool_calls.read().await;
         calls.iter().rev().take(limit).cloned().collect()
     }
+
+    /// Get the most recent tool calls made by `caller`, newest first
+    #[allow(dead_code)]
+    pub async fn get_tool_calls_by_caller(&self, caller: &str, limit: usize) -> Vec<ToolCall> {
+        let calls = self.tool_calls.read().await;
+        calls
+            .iter()
+            .rev()
+            .filter(|call| call.caller.as_deref() == Some(caller))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Size of the chunks a plain tool call log is read backwards in
+const LOG_TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Fix-up applied to a tool call log before new records are appended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogRepair {
+    None,
+    /// The plain log ends in a partial line; terminate it
+    TerminateLine,
+    /// The compressed log ends in a broken gzip segment; cut it off here
+    TruncateTo(u64),
+}
+
+/// Read the last `max_tool_calls` valid records from a tool call log.
+///
+/// Corrupt lines and unreadable gzip segments are skipped. Also reports how
+/// the file must be repaired before appending.
+async fn load_tool_call_log(
+    path: &Path,
+    compress: bool,
+    max_tool_calls: usize,
+) -> std::io::Result<(VecDeque<ToolCall>, LogRepair)> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok((VecDeque::new(), LogRepair::None))
+            }
+            Err(e) => return Err(e),
+        };
+        if compress {
+            read_compressed_log(file, &path, max_tool_calls)
+        } else {
+            read_plain_log_tail(file, &path, max_tool_calls)
+        }
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/// Parse one log line, warning about and skipping corrupt records
+fn parse_tool_call_line(line: &[u8], path: &Path) -> Option<ToolCall> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    serde_json::from_slice(line)
+        .map_err(|e| {
+            tracing::warn!(
+                "Skipping corrupt tool call log line in {}: {e}",
+                path.display()
+            )
+        })
+        .ok()
+}
+
+/// Read a plain log backwards from the end until enough records are found
+fn read_plain_log_tail(
+    mut file: std::fs::File,
+    path: &Path,
+    max_tool_calls: usize,
+) -> std::io::Result<(VecDeque<ToolCall>, LogRepair)> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let len = file.metadata()?.len();
+    let mut repair = LogRepair::None;
+    if len > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::Start(len - 1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            repair = LogRepair::TerminateLine;
+        }
+    }
+
+    // A call is logged when it starts and again when it completes. Reading
+    // backwards sees the completed record first; it takes the slot of the
+    // call's earliest record, as in the live history.
+    let mut newest_first: Vec<Option<ToolCall>> = Vec::new();
+    let mut slots: HashMap<Uuid, usize> = HashMap::new();
+    let mut pos = len;
+    // Start of the line that straddles the previous chunk boundary
+    let mut carry = Vec::new();
+    while pos > 0 && slots.len() < max_tool_calls {
+        let start = pos.saturating_sub(LOG_TAIL_CHUNK_BYTES);
+        let mut chunk = vec![0u8; (pos - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&carry);
+        pos = start;
+
+        let mut lines = chunk.split(|b| *b == b'\n');
+        // Unless this is the start of the file, the first line is incomplete
+        let head = if pos > 0 { lines.next() } else { None };
+        for call in lines
+            .rev()
+            .filter_map(|line| parse_tool_call_line(line, path))
+        {
+            match slots.get(&call.id) {
+                Some(&slot) => {
+                    let latest = newest_first[slot].take();
+                    slots.insert(call.id, newest_first.len());
+                    newest_first.push(latest);
+                }
+                None if slots.len() < max_tool_calls => {
+                    slots.insert(call.id, newest_first.len());
+                    newest_first.push(Some(call));
+                }
+                None => break,
+            }
+        }
+        carry = head.map(<[u8]>::to_vec).unwrap_or_default();
+    }
+
+    Ok((newest_first.into_iter().rev().flatten().collect(), repair))
+}
+
+/// Decode a compressed log one gzip segment at a time, keeping only the
+/// newest records
+fn read_compressed_log(
+    file: std::fs::File,
+    path: &Path,
+    max_tool_calls: usize,
+) -> std::io::Result<(VecDeque<ToolCall>, LogRepair)> {
+    use std::io::{BufRead, Read, Seek};
+
+    let len = file.metadata()?.len();
+    let mut reader = std::io::BufReader::new(file);
+    let mut history = VecDeque::with_capacity(max_tool_calls);
+    // End of the last segment that decoded cleanly
+    let mut good_end = 0;
+    let mut segment = Vec::new();
+    while !reader.fill_buf()?.is_empty() {
+        segment.clear();
+        // Keep whatever decoded before a broken segment
+        let result = flate2::bufread::GzDecoder::new(&mut reader).read_to_end(&mut segment);
+        for line in segment.split(|b| *b == b'\n') {
+            if let Some(call) = parse_tool_call_line(line, path) {
+                push_bounded(&mut history, call, max_tool_calls);
+            }
+        }
+        match result {
+            Ok(_) => good_end = reader.stream_position()?,
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping corrupt gzip segment in tool call log {}: {e}",
+                    path.display()
+                );
+                if !seek_next_gzip_segment(&mut reader)? {
+                    break;
+                }
+            }
+        }
+    }
+
+    let repair = if good_end < len {
+        LogRepair::TruncateTo(good_end)
+    } else {
+        LogRepair::None
+    };
+    Ok((history, repair))
+}
+
+/// Position `reader` at the next gzip header, returning false at end of file
+fn seek_next_gzip_segment<R: std::io::Read + std::io::Seek>(
+    reader: &mut R,
+) -> std::io::Result<bool> {
+    use std::io::SeekFrom;
+
+    const GZIP_MAGIC: [u8; 3] = [0x1f, 0x8b, 0x08];
+    let mut window = [0u8; 3];
+    let mut byte = [0u8; 1];
+    let mut read = 0u64;
+    loop {
+        if reader.read(&mut byte)? == 0 {
+            return Ok(false);
+        }
+        read += 1;
+        window = [window[1], window[2], byte[0]];
+        if read >= 3 && window == GZIP_MAGIC {
+            reader.seek(SeekFrom::Current(-3))?;
+            return Ok(true);
+        }
+    }
+}
+
+/// Serialize tool calls as newline-terminated JSON lines
+fn tool_call_lines<'a>(calls: impl IntoIterator<Item = &'a ToolCall>) -> Vec<u8> {
+    let mut lines = Vec::new();
+    for call in calls {
+        match serde_json::to_vec(call) {
+            Ok(line) => {
+                lines.extend_from_slice(&line);
+                lines.push(b'\n');
+            }
+            Err(e) => tracing::warn!("Failed to serialize tool call {}: {e}", call.id),
+        }
+    }
+    lines
+}
+
+/// Compress `data` as a single, self-contained gzip segment
+pub(crate) fn gzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Append tool calls to the log as they arrive, one JSON object per line.
+///
+/// Calls queued together are written as one batch, which in compressed mode
+/// becomes one gzip segment.
+async fn write_tool_call_log(
+    mut file: tokio::fs::File,
+    mut log_rx: mpsc::UnboundedReceiver<ToolCall>,
+    path: PathBuf,
+    compress: bool,
+) {
+    const MAX_BATCH: usize = 256;
+
+    while let Some(call) = log_rx.recv().await {
+        let mut batch = vec![call];
+        while batch.len() < MAX_BATCH {
+            match log_rx.try_recv() {
+                Ok(call) => batch.push(call),
+                Err(_) => break,
+            }
+        }
+
+        let lines = tool_call_lines(&batch);
+        if let Err(e) = async {
+            let bytes = if compress { gzip(&lines)? } else { lines };
+            file.write_all(&bytes).await?;
+            file.flush().await
+        }
+        .await
+        {
+            tracing::warn!("Failed to persist tool calls to {}: {e}", path.display());
+        }
+    }
+}
+
+/// Copy broadcast events into the bounded event history
+async fn record_events(
+    events: Arc<RwLock<VecDeque<RecordedEvent>>>,
+    mut event_rx: broadcast::Receiver<SystemEvent>,
+    capacity: usize,
+) {
+    loop {
+        match event_rx.recv().await {
+            Ok(event) => {
+                let mut events = events.write().await;
+                while events.len() >= capacity {
+                    events.pop_front();
+                }
+                events.push_back(RecordedEvent {
+                    timestamp: Utc::now(),
+                    event,
+                });
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("Event history fell behind, {skipped} events were not recorded");
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// The last broadcast event, for suppressing consecutive duplicates
+struct EventDedup {
+    window: Duration,
+    last: Mutex<Option<(SystemEvent, Instant)>>,
+}
+
+impl EventDedup {
+    /// Whether `event` repeats the last broadcast event within the window;
+    /// otherwise it becomes the new last event
+    fn is_duplicate(&self, event: &SystemEvent) -> bool {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        if let Some((previous, sent_at)) = last.as_ref() {
+            if previous == event && now.duration_since(*sent_at) < self.window {
+                return true;
+            }
+        }
+        *last = Some((event.clone(), now));
+        false
+    }
+}
+
+/// Token bucket per event type limiting broadcast rate
+struct EventThrottle {
+    per_second: f64,
+    buckets: Mutex<HashMap<&'static str, (f64, Instant)>>,
+}
+
+impl EventThrottle {
+    /// Take a token for `event_type`, returning false if none is left
+    fn allow(&self, event_type: &'static str) -> bool {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let (tokens, updated) = buckets.entry(event_type).or_insert((self.per_second, now));
+        let elapsed = now.duration_since(*updated).as_secs_f64();
+        *tokens = (*tokens + elapsed * self.per_second).min(self.per_second);
+        *updated = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Append to the history, evicting the oldest calls once it is full.
+///
+/// A completed call replaces the pending entry with the same id in place.
+fn push_bounded(tool_calls: &mut VecDeque<ToolCall>, call: ToolCall, max_tool_calls: usize) {
+    if call.result.is_some() {
+        if let Some(existing) = tool_calls.iter_mut().rev().find(|c| c.id == call.id) {
+            *existing = call;
+            return;
+        }
+    }
+    while tool_calls.len() >= max_tool_calls {
+        tool_calls.pop_front();
+    }
+    tool_calls.push_back(call);
+}
+
+/// Drain queued tool calls into the history, taking the write lock once per batch
+async fn flush_tool_call_batches(
+    tool_calls: Arc<RwLock<VecDeque<ToolCall>>>,
+    mut batch_rx: mpsc::UnboundedReceiver<ToolCall>,
+    max_tool_calls: usize,
+) {
+    const MAX_BATCH: usize = 256;
+
+    while let Some(call) = batch_rx.recv().await {
+        let mut batch = vec![call];
+        while batch.len() < MAX_BATCH {
+            match batch_rx.try_recv() {
+                Ok(call) => batch.push(call),
+                Err(_) => break,
+            }
+        }
+
+        let mut tool_calls = tool_calls.write().await;
+        for call in batch {
+            push_bounded(&mut tool_calls, call, max_tool_calls);
+        }
+    }
 }
 
 impl Default for AppState {
@@ -208,6 +1098,17 @@ pub struct McpStatus {
     pub started_at: DateTime<Utc>,
 }
 
+impl McpStatus {
+    /// Whether the heartbeat is missing or older than `HEARTBEAT_STALE_AFTER`
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.last_heartbeat.is_none_or(|heartbeat| {
+            now.signed_duration_since(heartbeat)
+                .to_std()
+                .is_ok_and(|age| age > HEARTBEAT_STALE_AFTER)
+        })
+    }
+}
+
 impl Default for McpStatus {
     fn default() -> Self {
         Self {
@@ -267,8 +1168,6 @@ impl SessionInfo {
     }
 
     /// Update session with new activity
-    // Allow dead_code: Session management infrastructure for tracking user activity and request counts
-    #[allow(dead_code)]
     pub fn update_activity(&mut self) {
         self.last_activity = Utc::now();
         self.request_count += 1;
@@ -311,6 +1210,15 @@ pub struct ToolCall {
     pub success: bool,
     /// Error message
     pub error: Option<String>,
+    /// Who made the call (agent, tenant or user), if provided
+    #[serde(default)]
+    pub caller: Option<String>,
+    /// Free-form tags attached by the caller
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Id of the dashboard HTTP request that made the call, if any
+    #[serde(default)]
+    pub request_id: Option<String>,
 }
 
 impl ToolCall {
@@ -328,9 +1236,19 @@ impl ToolCall {
             result_string: None,
             success: false,
             error: None,
+            caller: None,
+            tags: Vec::new(),
+            request_id: None,
         }
     }
 
+    /// Attach caller attribution and tags
+    pub fn with_metadata(mut self, caller: Option<String>, tags: Vec<String>) -> Self {
+        self.caller = caller;
+        self.tags = tags;
+        self
+    }
+
     /// Mark tool call as completed with result
     pub fn complete(mut self, result: ToolCallResult, duration_ms: u64) -> Self {
         self.result = Some(result.clone());
@@ -365,7 +1283,7 @@ pub enum ToolCallResult {
 }
 
 /// System events for real-time updates
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../static/js/types/generated/")]
 pub enum SystemEvent {
     /// MCP server connected
@@ -382,10 +1300,211 @@ pub enum SystemEvent {
     ResourceAccessed { uri: String },
     /// System error occurred
     Error { message: String },
+    /// An MCP client session started
+    SessionStarted {
+        #[ts(type = "string")]
+        id: Uuid,
+    },
+    /// An MCP client session was removed after being idle
+    SessionExpired {
+        #[ts(type = "string")]
+        id: Uuid,
+    },
     /// Custom event for hot-reload and other purposes
     Custom(String),
 }
 
+impl SystemEvent {
+    /// Event type name used by the WebSocket and SSE streams
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            SystemEvent::McpConnected => "mcp_connected",
+            SystemEvent::McpDisconnected => "mcp_disconnected",
+            SystemEvent::ToolCalled { .. } => "tool_called",
+            SystemEvent::ResourceAccessed { .. } => "resource_accessed",
+            SystemEvent::Error { .. } => "error",
+            SystemEvent::SessionStarted { .. } => "session_started",
+            SystemEvent::SessionExpired { .. } => "session_expired",
+            SystemEvent::Custom(_) => "custom",
+        }
+    }
+
+    /// JSON message sent to WebSocket clients.
+    ///
+    /// Custom payloads that are already JSON are passed through unchanged.
+    pub fn to_ws_json(&self, timestamp_format: TimestampFormat) -> serde_json::Value {
+        let timestamp = timestamp_format.format(Utc::now());
+        match self {
+            SystemEvent::McpConnected | SystemEvent::McpDisconnected => serde_json::json!({
+                "type": self.event_type(),
+                "timestamp": timestamp
+            }),
+            SystemEvent::ToolCalled { name, id } => serde_json::json!({
+                "type": self.event_type(),
+                "name": name,
+                "id": id,
+                "timestamp": timestamp
+            }),
+            SystemEvent::ResourceAccessed { uri } => serde_json::json!({
+                "type": self.event_type(),
+                "uri": uri,
+                "timestamp": timestamp
+            }),
+            SystemEvent::Error { message } => serde_json::json!({
+                "type": self.event_type(),
+                "message": message,
+                "timestamp": timestamp
+            }),
+            SystemEvent::SessionStarted { id } | SystemEvent::SessionExpired { id } => {
+                serde_json::json!({
+                    "type": self.event_type(),
+                    "id": id,
+                    "timestamp": timestamp
+                })
+            }
+            SystemEvent::Custom(payload) => serde_json::from_str(payload).unwrap_or_else(|_| {
+                serde_json::json!({
+                    "type": self.event_type(),
+                    "payload": payload,
+                    "timestamp": timestamp
+                })
+            }),
+        }
+    }
+
+    /// WebSocket message for an event standing in for `count` debounced
+    /// events of its type; a `coalesced` count is added when above one.
+    pub fn to_coalesced_ws_json(
+        &self,
+        timestamp_format: TimestampFormat,
+        count: u32,
+    ) -> serde_json::Value {
+        let mut json = self.to_ws_json(timestamp_format);
+        add_coalesced_count(&mut json, count);
+        json
+    }
+
+    /// Server-sent event frame, including the HTML fragment htmx swaps into
+    /// the dashboard
+    #[allow(dead_code)]
+    pub fn to_sse_frame(&self, timestamp_format: TimestampFormat) -> String {
+        self.to_coalesced_sse_frame(timestamp_format, 1)
+    }
+
+    /// Server-sent event frame for an event standing in for `count`
+    /// debounced events of its type
+    pub fn to_coalesced_sse_frame(&self, timestamp_format: TimestampFormat, count: u32) -> String {
+        let now = Utc::now();
+        let timestamp = timestamp_format.format(now);
+        let time = now.format("%H:%M:%S");
+
+        let mut data = match self {
+            SystemEvent::McpConnected => serde_json::json!({
+                "type": "connected",
+                "timestamp": timestamp,
+                "html": format!(
+                    r#"<div class="alert alert-success" hx-swap-oob="afterbegin:#events-container">
+                                <span class="timestamp">{time}</span>
+                                <span class="message">MCP Server Connected</span>
+                            </div>"#
+                )
+            }),
+            SystemEvent::McpDisconnected => serde_json::json!({
+                "type": "disconnected",
+                "timestamp": timestamp,
+                "html": format!(
+                    r#"<div class="alert alert-warning" hx-swap-oob="afterbegin:#events-container">
+                                <span class="timestamp">{time}</span>
+                                <span class="message">MCP Server Disconnected</span>
+                            </div>"#
+                )
+            }),
+            SystemEvent::ToolCalled { name, id } => serde_json::json!({
+                "type": "tool_called",
+                "name": name,
+                "id": id,
+                "timestamp": timestamp,
+                "html": format!(
+                    r#"<div class="tool-call-event" hx-swap-oob="afterbegin:#tool-calls-live">
+                                <div class="tool-call">
+                                    <span class="timestamp">{time}</span>
+                                    <span class="tool-name">{name}</span>
+                                    <span class="tool-id">#{id}</span>
+                                    <span class="status executing">Executing...</span>
+                                </div>
+                            </div>"#
+                )
+            }),
+            SystemEvent::ResourceAccessed { uri } => serde_json::json!({
+                "type": "resource_accessed",
+                "uri": uri,
+                "timestamp": timestamp,
+                "html": format!(
+                    r#"<div class="resource-event" hx-swap-oob="afterbegin:#resources-live">
+                                <span class="timestamp">{time}</span>
+                                <span class="resource-uri">{uri}</span>
+                                <span class="status accessed">Accessed</span>
+                            </div>"#
+                )
+            }),
+            SystemEvent::Error { message } => serde_json::json!({
+                "type": "error",
+                "message": message,
+                "timestamp": timestamp,
+                "html": format!(
+                    r#"<div class="alert alert-error" hx-swap-oob="afterbegin:#events-container">
+                                <span class="timestamp">{time}</span>
+                                <span class="message">Error: {message}</span>
+                            </div>"#
+                )
+            }),
+            SystemEvent::SessionStarted { id } => serde_json::json!({
+                "type": "session_started",
+                "id": id,
+                "timestamp": timestamp,
+                "html": format!(
+                    r#"<div class="alert alert-info" hx-swap-oob="afterbegin:#events-container">
+                                <span class="timestamp">{time}</span>
+                                <span class="message">Session {id} started</span>
+                            </div>"#
+                )
+            }),
+            SystemEvent::SessionExpired { id } => serde_json::json!({
+                "type": "session_expired",
+                "id": id,
+                "timestamp": timestamp,
+                "html": format!(
+                    r#"<div class="alert alert-info" hx-swap-oob="afterbegin:#events-container">
+                                <span class="timestamp">{time}</span>
+                                <span class="message">Session {id} expired</span>
+                            </div>"#
+                )
+            }),
+            SystemEvent::Custom(_) => self.to_ws_json(timestamp_format),
+        };
+        add_coalesced_count(&mut data, count);
+
+        format!("event: {}\ndata: {data}\n\n", self.event_type())
+    }
+}
+
+/// Record how many events a debounced frame stands for, so the UI can show
+/// "x5". Custom payloads that are not JSON objects are left unchanged.
+fn add_coalesced_count(data: &mut serde_json::Value, count: u32) {
+    if count > 1 {
+        if let Some(object) = data.as_object_mut() {
+            object.insert("coalesced".to_string(), count.into());
+        }
+    }
+}
+
+/// A system event and when it was broadcast
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub timestamp: DateTime<Utc>,
+    pub event: SystemEvent,
+}
+
 /// Extended system event with full details (for compatibility)
 // Allow dead_code: Rich event type with metadata for future event system extensions
 #[allow(dead_code)]
@@ -403,6 +1522,105 @@ pub struct SystemEventDetails {
     pub metadata: serde_json::Value,
 }
 
+/// Invocation statistics for one tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolUsage {
+    pub name: String,
+    pub calls: usize,
+    /// Percentage of calls that succeeded, rounded to one decimal place
+    pub success_rate: f64,
+    pub avg_duration_ms: f64,
+}
+
+/// When a tool last completed successfully and last failed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolHealth {
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_error: Option<DateTime<Utc>>,
+    pub last_error_message: Option<String>,
+}
+
+/// Structured metric identifier: metric name, owning tool and extra labels
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct MetricKey {
+    /// Metric name (e.g. `tool_calls`)
+    pub name: String,
+    /// Tool the metric belongs to, if any
+    pub tool: Option<String>,
+    /// Additional labels
+    pub labels: BTreeMap<String, String>,
+}
+
+impl MetricKey {
+    /// Create a key for a server-wide metric
+    #[allow(dead_code)]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            tool: None,
+            labels: BTreeMap::new(),
+        }
+    }
+
+    /// Create a key for a metric belonging to a tool
+    pub fn for_tool(tool: &str, name: &str) -> Self {
+        Self {
+            tool: Some(tool.to_string()),
+            ..Self::new(name)
+        }
+    }
+
+    /// Add a label to the key
+    #[allow(dead_code)]
+    pub fn with_label(mut self, key: &str, value: &str) -> Self {
+        self.labels.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Render as `name{tool="...",label="..."}`, omitting braces when unlabeled
+    pub fn labeled_name(&self) -> String {
+        let labels: Vec<String> = self
+            .tool
+            .iter()
+            .map(|tool| ("tool", tool.as_str()))
+            .chain(self.labels.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .map(|(k, v)| format!("{k}=\"{}\"", escape_label_value(v)))
+            .collect();
+
+        let name = prometheus_name(&self.name);
+        if labels.is_empty() {
+            name
+        } else {
+            format!("{name}{{{}}}", labels.join(","))
+        }
+    }
+}
+
+/// Replace characters Prometheus does not allow in metric names
+fn prometheus_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
 /// Different types of metrics that can be collected
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../static/js/types/generated/")]
@@ -417,8 +1635,6 @@ pub enum MetricValue {
 
 impl MetricValue {
     /// Get the current value as a number
-    // Allow dead_code: Utility for metric aggregation and display, converts all metric types to unified numeric value
-    #[allow(dead_code)]
     pub fn as_number(&self) -> f64 {
         match self {
             MetricValue::Counter(c) => *c as f64,
@@ -426,6 +1642,62 @@ impl MetricValue {
             MetricValue::Histogram(h) => h.last().copied().unwrap_or(0.0),
         }
     }
+
+    /// Summary statistics of the recorded samples.
+    ///
+    /// Counters and gauges are summarized as a single sample.
+    pub fn summary(&self) -> HistogramSummary {
+        match self {
+            MetricValue::Counter(_) | MetricValue::Gauge(_) => {
+                HistogramSummary::from_samples(&[self.as_number()])
+            }
+            MetricValue::Histogram(samples) => HistogramSummary::from_samples(samples),
+        }
+    }
+}
+
+/// Count, extremes, mean and percentiles of a histogram's samples
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct HistogramSummary {
+    pub count: usize,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl HistogramSummary {
+    /// Summarize `samples`; all fields are 0 when there are none.
+    ///
+    /// Percentiles use the nearest-rank method, so they are always one of the
+    /// recorded samples.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+            sorted[rank.clamp(1, sorted.len()) - 1]
+        };
+
+        let sum: f64 = sorted.iter().sum();
+        Self {
+            count: sorted.len(),
+            sum,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean: sum / sorted.len() as f64,
+            p50: percentile(50.0),
+            p95: percentile(95.0),
+            p99: percentile(99.0),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -460,6 +1732,155 @@ mod tests {
         assert_eq!(metric.as_number(), 1.0);
     }
 
+    #[tokio::test]
+    async fn test_structured_tool_metrics() {
+        let state = AppState::new();
+
+        for name in ["alpha", "alpha", "beta"] {
+            let call = ToolCall::new(name.to_string(), serde_json::json!({}));
+            state.record_tool_call(call).await.unwrap();
+        }
+        state.increment_counter(MetricKey::for_tool("beta", "errors").with_label("kind", "io"));
+
+        // Per-tool lookup by structured key, not by parsing strings
+        let alpha = state.tool_metrics("alpha");
+        assert_eq!(alpha.len(), 1);
+        assert_eq!(alpha[0].0, MetricKey::for_tool("alpha", "tool_calls"));
+        assert_eq!(alpha[0].1.as_number(), 2.0);
+        assert_eq!(state.tool_metrics("beta").len(), 2);
+
+        let exported = state.export_prometheus();
+        let lines: Vec<&str> = exported.lines().collect();
+        assert!(lines.contains(&"# TYPE tool_calls counter"));
+        assert!(lines.contains(&"tool_calls{tool=\"alpha\"} 2"));
+        assert!(lines.contains(&"errors{tool=\"beta\",kind=\"io\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_filtered_by_caller() {
+        let state = AppState::new();
+
+        for (caller, tags) in [
+            ("agent-a", vec!["batch"]),
+            ("agent-b", vec![]),
+            ("agent-a", vec![]),
+        ] {
+            let call = ToolCall::new("file_search".to_string(), serde_json::json!({}))
+                .with_metadata(
+                    Some(caller.to_string()),
+                    tags.into_iter().map(String::from).collect(),
+                );
+            state.record_tool_call(call).await.unwrap();
+        }
+        state
+            .record_tool_call(ToolCall::new(
+                "file_search".to_string(),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+
+        let calls = state.get_tool_calls_by_caller("agent-a", 10).await;
+        assert_eq!(calls.len(), 2);
+        assert!(calls.iter().all(|c| c.caller.as_deref() == Some("agent-a")));
+        // Newest first
+        assert!(calls[0].tags.is_empty());
+        assert_eq!(calls[1].tags, vec!["batch".to_string()]);
+
+        assert_eq!(state.get_tool_calls_by_caller("agent-b", 10).await.len(), 1);
+        assert!(state
+            .get_tool_calls_by_caller("nobody", 10)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_history_is_bounded() {
+        let state = AppState::new();
+
+        for i in 0..1500 {
+            let call = ToolCall::new(format!("tool_{i}"), serde_json::json!({}));
+            state.record_tool_call(call).await.unwrap();
+        }
+
+        let calls = state.tool_calls.read().await;
+        assert_eq!(calls.len(), 1000);
+        // The oldest 500 calls were evicted
+        assert_eq!(calls.front().unwrap().name, "tool_500");
+        assert_eq!(calls.back().unwrap().name, "tool_1499");
+        drop(calls);
+
+        // Newest-first reads keep working
+        let recent = state.get_tool_calls(2).await;
+        assert_eq!(recent[0].name, "tool_1499");
+        assert_eq!(recent[1].name, "tool_1498");
+
+        let small = AppState::with_history_limit(3);
+        for i in 0..5 {
+            small
+                .add_tool_call(ToolCall::new(format!("tool_{i}"), serde_json::json!({})))
+                .await;
+        }
+        assert_eq!(small.tool_calls.read().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_batched_tool_call_recording() {
+        let state = AppState::new().with_tool_call_batching();
+
+        for i in 0..1500 {
+            let call = ToolCall::new(format!("tool_{i}"), serde_json::json!({}));
+            state.record_tool_call(call).await.unwrap();
+        }
+
+        for _ in 0..100 {
+            let done = state
+                .tool_calls
+                .read()
+                .await
+                .back()
+                .is_some_and(|call| call.name == "tool_1499");
+            if done {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let calls = state.tool_calls.read().await;
+        assert_eq!(calls.len(), 1000);
+        for (i, call) in calls.iter().enumerate() {
+            assert_eq!(call.name, format!("tool_{}", i + 500));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_history_newest_first() {
+        let state = AppState::new().with_event_history(DEFAULT_EVENT_HISTORY);
+
+        state.event_tx.send(SystemEvent::McpConnected).unwrap();
+        state
+            .event_tx
+            .send(SystemEvent::Custom("reload".to_string()))
+            .unwrap();
+        state.event_tx.send(SystemEvent::McpDisconnected).unwrap();
+
+        for _ in 0..100 {
+            if state.events.read().await.len() == 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let events = state.get_events(10).await;
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0].event, SystemEvent::McpDisconnected));
+        assert!(matches!(&events[1].event, SystemEvent::Custom(msg) if msg == "reload"));
+        assert!(matches!(events[2].event, SystemEvent::McpConnected));
+        assert!(events[0].timestamp >= events[2].timestamp);
+
+        assert_eq!(state.get_events(1).await.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_session_management() {
         let state = AppState::new();
@@ -544,4 +1965,320 @@ mod tests {
         assert_eq!(state.tool_calls.read().await.len(), 10);
         assert_eq!(state.active_sessions.len(), 10);
     }
+
+    #[tokio::test]
+    async fn test_event_dedup_suppresses_consecutive_duplicates() {
+        let state = AppState::new().with_event_dedup(Duration::from_secs(60));
+        let mut rx = state.event_tx.subscribe();
+
+        for _ in 0..5 {
+            state.broadcast(SystemEvent::McpConnected);
+        }
+        assert!(state.broadcast(SystemEvent::Error {
+            message: "disk full".to_string()
+        }));
+        assert!(!state.broadcast(SystemEvent::Error {
+            message: "disk full".to_string()
+        }));
+        // Not consecutive any more, so it is delivered again
+        assert!(state.broadcast(SystemEvent::McpConnected));
+
+        let mut received = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            received.push(event);
+        }
+        assert_eq!(
+            received,
+            vec![
+                SystemEvent::McpConnected,
+                SystemEvent::Error {
+                    message: "disk full".to_string()
+                },
+                SystemEvent::McpConnected,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_event_dedup_window_expires() {
+        let state = AppState::new().with_event_dedup(Duration::from_millis(20));
+
+        assert!(state.broadcast(SystemEvent::McpConnected));
+        assert!(!state.broadcast(SystemEvent::McpConnected));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(state.broadcast(SystemEvent::McpConnected));
+
+        // Disabled by default
+        let state = AppState::new();
+        assert!(state.broadcast(SystemEvent::McpConnected));
+        assert!(state.broadcast(SystemEvent::McpConnected));
+    }
+
+    #[tokio::test]
+    async fn test_event_rate_limit_caps_each_type() {
+        let state = AppState::new().with_event_rate_limit(5);
+        let mut rx = state.event_tx.subscribe();
+
+        let delivered = (0..50)
+            .filter(|i| {
+                state.broadcast(SystemEvent::Error {
+                    message: format!("error {i}"),
+                })
+            })
+            .count();
+        assert_eq!(delivered, 5);
+        // Other types have their own budget
+        assert!(state.broadcast(SystemEvent::McpConnected));
+
+        let mut received = 0;
+        while rx.try_recv().is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, 6);
+
+        // The bucket refills over time
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(state.broadcast(SystemEvent::Error {
+            message: "later".to_string()
+        }));
+
+        // Disabled by default
+        let state = AppState::new();
+        assert!((0..50).all(|_| state.broadcast(SystemEvent::McpConnected)));
+    }
+
+    fn sse_data(frame: &str) -> serde_json::Value {
+        let data = frame
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .expect("frame should have a data line");
+        serde_json::from_str(data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_expire() {
+        let state = AppState::new();
+        let mut events = state.subscribe_to_events();
+
+        let mut idle = SessionInfo::new();
+        idle.last_activity = Utc::now() - chrono::Duration::minutes(10);
+        let idle_id = idle.id;
+        state.active_sessions.insert(idle_id, idle);
+        let active_id = state.start_session();
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SystemEvent::SessionStarted { id: active_id }
+        );
+
+        let expired = state.expire_idle_sessions(Duration::from_secs(60));
+        assert_eq!(expired, vec![idle_id]);
+        assert!(!state.active_sessions.contains_key(&idle_id));
+        assert!(state.touch_session(active_id));
+        assert_eq!(
+            state.active_sessions.get(&active_id).unwrap().request_count,
+            1
+        );
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SystemEvent::SessionExpired { id: idle_id }
+        );
+    }
+
+    #[test]
+    fn test_ws_json_shape_per_variant() {
+        let id = Uuid::new_v4();
+        let format = TimestampFormat::Rfc3339;
+
+        let json = SystemEvent::McpConnected.to_ws_json(format);
+        assert_eq!(json["type"], "mcp_connected");
+        assert!(json["timestamp"].is_string());
+
+        let json = SystemEvent::McpDisconnected.to_ws_json(format);
+        assert_eq!(json["type"], "mcp_disconnected");
+
+        let json = SystemEvent::ToolCalled {
+            name: "file_search".to_string(),
+            id,
+        }
+        .to_ws_json(format);
+        assert_eq!(json["type"], "tool_called");
+        assert_eq!(json["name"], "file_search");
+        assert_eq!(json["id"], id.to_string());
+
+        let json = SystemEvent::ResourceAccessed {
+            uri: "registry://tools".to_string(),
+        }
+        .to_ws_json(format);
+        assert_eq!(json["type"], "resource_accessed");
+        assert_eq!(json["uri"], "registry://tools");
+
+        let json = SystemEvent::Error {
+            message: "disk full".to_string(),
+        }
+        .to_ws_json(TimestampFormat::EpochMillis);
+        assert_eq!(json["type"], "error");
+        assert_eq!(json["message"], "disk full");
+        assert!(json["timestamp"].is_i64());
+    }
+
+    #[test]
+    fn test_custom_payload_handling_is_shared() {
+        let format = TimestampFormat::Rfc3339;
+
+        let reload = SystemEvent::Custom(r#"{"type":"reload","action":"refresh"}"#.to_string());
+        assert_eq!(
+            reload.to_ws_json(format),
+            serde_json::json!({ "type": "reload", "action": "refresh" })
+        );
+        assert_eq!(
+            sse_data(&reload.to_sse_frame(format)),
+            reload.to_ws_json(format)
+        );
+
+        let plain = SystemEvent::Custom("not json".to_string());
+        let json = plain.to_ws_json(format);
+        assert_eq!(json["type"], "custom");
+        assert_eq!(json["payload"], "not json");
+
+        let frame = plain.to_sse_frame(format);
+        assert!(frame.starts_with("event: custom\n"));
+        assert_eq!(sse_data(&frame)["payload"], "not json");
+    }
+
+    #[test]
+    fn test_sse_frame_shape_per_variant() {
+        let format = TimestampFormat::Rfc3339;
+        let cases = [
+            (SystemEvent::McpConnected, "mcp_connected", "connected"),
+            (
+                SystemEvent::McpDisconnected,
+                "mcp_disconnected",
+                "disconnected",
+            ),
+            (
+                SystemEvent::ToolCalled {
+                    name: "file_search".to_string(),
+                    id: Uuid::new_v4(),
+                },
+                "tool_called",
+                "tool_called",
+            ),
+            (
+                SystemEvent::ResourceAccessed {
+                    uri: "registry://tools".to_string(),
+                },
+                "resource_accessed",
+                "resource_accessed",
+            ),
+            (
+                SystemEvent::Error {
+                    message: "disk full".to_string(),
+                },
+                "error",
+                "error",
+            ),
+            (
+                SystemEvent::SessionStarted { id: Uuid::new_v4() },
+                "session_started",
+                "session_started",
+            ),
+            (
+                SystemEvent::SessionExpired { id: Uuid::new_v4() },
+                "session_expired",
+                "session_expired",
+            ),
+        ];
+
+        for (event, name, data_type) in cases {
+            let frame = event.to_sse_frame(format);
+            assert!(frame.starts_with(&format!("event: {name}\ndata: ")));
+            assert!(frame.ends_with("\n\n"));
+
+            let data = sse_data(&frame);
+            assert_eq!(data["type"], data_type);
+            assert!(data["timestamp"].is_string());
+            assert!(data["html"].as_str().unwrap().contains("hx-swap-oob"));
+        }
+    }
+
+    #[test]
+    fn test_histogram_summary() {
+        // 1..=100 shuffled so sorting is exercised
+        let samples: Vec<f64> = (1..=100).map(|i| ((i * 37) % 100 + 1) as f64).collect();
+        let summary = MetricValue::Histogram(samples).summary();
+
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.sum, 5050.0);
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.max, 100.0);
+        assert_eq!(summary.mean, 50.5);
+        assert_eq!(summary.p50, 50.0);
+        assert_eq!(summary.p95, 95.0);
+        assert_eq!(summary.p99, 99.0);
+
+        assert_eq!(MetricValue::Histogram(vec![2.0, 4.0]).summary().mean, 3.0);
+        assert_eq!(
+            MetricValue::Histogram(Vec::new()).summary(),
+            HistogramSummary::default()
+        );
+        assert_eq!(MetricValue::Gauge(7.5).summary().p99, 7.5);
+    }
+
+    #[test]
+    fn test_record_histogram_bounded() {
+        let state = AppState::new();
+        for i in 1..=2000 {
+            state.record_histogram("latency", i as f64);
+        }
+
+        // Release the map guard before the metric is updated below
+        {
+            let metric = state.metrics.get("latency").unwrap();
+            let MetricValue::Histogram(samples) = metric.value() else {
+                panic!("expected a histogram");
+            };
+            assert_eq!(samples.len(), MAX_HISTOGRAM_SAMPLES);
+            assert!(samples.capacity() <= 2 * MAX_HISTOGRAM_SAMPLES);
+            // Only the most recent samples (977..=2000) are kept
+            assert_eq!(samples[0], 977.0);
+
+            let summary = metric.summary();
+            assert_eq!(summary.max, 2000.0);
+            assert_eq!(summary.p50, 1488.0);
+            assert_eq!(summary.p99, 1990.0);
+        }
+
+        // Non-histogram metrics are replaced
+        state.update_metric("latency", MetricValue::Gauge(1.0));
+        state.record_histogram("latency", 5.0);
+        assert!(matches!(
+            state.metrics.get("latency").as_deref(),
+            Some(MetricValue::Histogram(samples)) if samples == &vec![5.0]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_stamped_only_while_connected() {
+        let state = AppState::new();
+        state.mcp_status.store(Arc::new(McpStatus {
+            connected: false,
+            last_heartbeat: None,
+            ..McpStatus::default()
+        }));
+        let heartbeat = state.spawn_heartbeat(Duration::from_millis(10));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(state.mcp_status.load().last_heartbeat.is_none());
+        assert!(state.mcp_status.load().is_stale(Utc::now()));
+
+        state.set_mcp_connected(true);
+        for _ in 0..100 {
+            if state.mcp_status.load().last_heartbeat.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!state.mcp_status.load().is_stale(Utc::now()));
+        heartbeat.abort();
+    }
 }
@@ -0,0 +1,276 @@
+//! Supervision of the MCP and dashboard servers.
+//!
+//! Each server runs under [`supervise`], which restarts a stopped server
+//! according to its [`RestartPolicy`] and [`Backoff`]. In `both` mode with
+//! independent lifecycles one server exiting leaves the other running; in
+//! `mcp` mode failures are retried with exponential backoff.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::shared::{config::RestartPolicy, state::AppState};
+
+/// Delay before a stopped server is started again
+pub const RESTART_BACKOFF: Backoff = Backoff::fixed(Duration::from_secs(1));
+
+/// Longest delay between restarts with exponential backoff
+pub const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Delay before restart `attempt` (from 0): `base` doubled per attempt, capped
+/// at `MAX_RESTART_BACKOFF`
+pub fn restart_backoff(base: Duration, attempt: u32) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_RESTART_BACKOFF)
+}
+
+/// How long [`supervise`] waits between restarts, and how many it allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// Delay before the first restart
+    pub base: Duration,
+    /// Double the delay after each restart, up to `MAX_RESTART_BACKOFF`
+    pub exponential: bool,
+    /// Give up after this many restarts; `None` restarts indefinitely
+    pub max_restarts: Option<u32>,
+}
+
+impl Backoff {
+    /// Wait `base` before every restart, without limit
+    pub const fn fixed(base: Duration) -> Self {
+        Self {
+            base,
+            exponential: false,
+            max_restarts: None,
+        }
+    }
+
+    /// Double the delay from `base` per restart, giving up after `max_restarts`
+    pub const fn exponential(base: Duration, max_restarts: u32) -> Self {
+        Self {
+            base,
+            exponential: true,
+            max_restarts: Some(max_restarts),
+        }
+    }
+
+    /// Delay before restart `attempt` (from 0)
+    fn delay(&self, attempt: u32) -> Duration {
+        if self.exponential {
+            restart_backoff(self.base, attempt)
+        } else {
+            self.base
+        }
+    }
+}
+
+/// Run the server built by `start`, restarting it per `policy` and `backoff`.
+///
+/// Returns the result of the last run once the policy stops restarting or
+/// the restart limit is reached.
+pub async fn supervise<F, Fut, E>(
+    name: &str,
+    policy: RestartPolicy,
+    backoff: Backoff,
+    mut start: F,
+) -> Result<(), E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    let mut restarts = 0;
+    loop {
+        let result = start().await;
+        match &result {
+            Ok(()) => tracing::info!("{name} server stopped"),
+            Err(e) => tracing::error!("{name} server error: {e}"),
+        }
+
+        let restart = match policy {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => result.is_err(),
+            RestartPolicy::Always => true,
+        };
+        if !restart {
+            return result;
+        }
+        if let Some(max_restarts) = backoff.max_restarts {
+            if restarts >= max_restarts {
+                if max_restarts > 0 {
+                    tracing::error!(
+                        "{name} server failed after {max_restarts} restarts, giving up"
+                    );
+                }
+                return result;
+            }
+        }
+
+        let delay = backoff.delay(restarts);
+        restarts += 1;
+        tracing::warn!("Restarting {name} server in {}ms", delay.as_millis());
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Run one MCP transport session, keeping `mcp_status.connected` in step.
+///
+/// The server is marked connected when the session starts (if it is not
+/// already) and disconnected once the transport closes or fails, so the
+/// dashboard sees `McpDisconnected` as soon as the client goes away.
+pub async fn track_mcp_connection<Fut, E>(state: &AppState, session: Fut) -> Result<(), E>
+where
+    Fut: Future<Output = Result<(), E>>,
+{
+    if !state.mcp_status.load().connected {
+        state.set_mcp_connected(true);
+    }
+    let result = session.await;
+    state.set_mcp_connected(false);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::sync::Notify;
+
+    #[tokio::test]
+    async fn test_mcp_keeps_running_after_dashboard_ends() {
+        let stop_mcp = Arc::new(Notify::new());
+        let dashboard_runs = Arc::new(AtomicUsize::new(0));
+
+        let mcp = supervise(
+            "MCP",
+            RestartPolicy::Never,
+            Backoff::fixed(Duration::ZERO),
+            {
+                let stop_mcp = stop_mcp.clone();
+                move || {
+                    let stop_mcp = stop_mcp.clone();
+                    async move {
+                        stop_mcp.notified().await;
+                        Ok::<(), String>(())
+                    }
+                }
+            },
+        );
+        let dashboard = supervise(
+            "dashboard",
+            RestartPolicy::Never,
+            Backoff::fixed(Duration::ZERO),
+            {
+                let dashboard_runs = dashboard_runs.clone();
+                move || {
+                    dashboard_runs.fetch_add(1, Ordering::SeqCst);
+                    async { Err::<(), String>("bind failed".to_string()) }
+                }
+            },
+        );
+        let servers = tokio::spawn(async move { tokio::join!(mcp, dashboard) });
+
+        // The dashboard has ended but the MCP server is still serving
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(dashboard_runs.load(Ordering::SeqCst), 1);
+        assert!(!servers.is_finished());
+
+        stop_mcp.notify_one();
+        let (mcp, dashboard) = servers.await.unwrap();
+        assert!(mcp.is_ok());
+        assert_eq!(dashboard.unwrap_err(), "bind failed");
+    }
+
+    #[tokio::test]
+    async fn test_on_failure_restarts_until_clean_exit() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let result = supervise(
+            "dashboard",
+            RestartPolicy::OnFailure,
+            Backoff::fixed(Duration::ZERO),
+            {
+                let runs = runs.clone();
+                move || {
+                    let run = runs.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        if run < 2 {
+                            Err("crashed".to_string())
+                        } else {
+                            Ok(())
+                        }
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_restart_backoff_doubles_up_to_cap() {
+        let base = Duration::from_millis(500);
+        assert_eq!(restart_backoff(base, 0), Duration::from_millis(500));
+        assert_eq!(restart_backoff(base, 1), Duration::from_millis(1000));
+        assert_eq!(restart_backoff(base, 3), Duration::from_millis(4000));
+        assert_eq!(restart_backoff(base, 40), MAX_RESTART_BACKOFF);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exponential_backoff_then_gives_up() {
+        use crate::shared::state::SystemEvent;
+        use tokio::time::Instant;
+
+        let state = AppState::new();
+        let mut events = state.subscribe_to_events();
+        let started = Instant::now();
+        let mut starts = Vec::new();
+
+        let backoff = Backoff::exponential(Duration::from_millis(100), 3);
+        let result = supervise("MCP", RestartPolicy::OnFailure, backoff, || {
+            starts.push(started.elapsed());
+            track_mcp_connection(&state, async {
+                Err::<(), String>("transport closed".to_string())
+            })
+        })
+        .await;
+
+        assert_eq!(result.unwrap_err(), "transport closed");
+        assert_eq!(
+            starts,
+            [0, 100, 300, 700].map(Duration::from_millis).to_vec()
+        );
+        assert!(!state.mcp_status.load().connected);
+
+        // Every failed run disconnects, every restart reconnects
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        let expected: Vec<_> = (0..4)
+            .flat_map(|run| {
+                let disconnected = std::iter::once(SystemEvent::McpDisconnected);
+                let reconnected = (run < 3).then_some(SystemEvent::McpConnected);
+                disconnected.chain(reconnected)
+            })
+            .collect();
+        assert_eq!(received, expected);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_clean_exit_not_restarted() {
+        let state = AppState::new();
+        let mut runs = 0;
+        let backoff = Backoff::exponential(Duration::ZERO, 3);
+        let result = supervise("MCP", RestartPolicy::OnFailure, backoff, || {
+            runs += 1;
+            track_mcp_connection(&state, async { Ok::<(), String>(()) })
+        })
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(runs, 1);
+    }
+}
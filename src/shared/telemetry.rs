@@ -0,0 +1,38 @@
+//! OpenTelemetry export of tracing spans.
+//!
+//! Tool calls (`tool_call`) and dashboard requests (`http_request`) are
+//! recorded as `tracing` spans; with the `otel` feature enabled and an OTLP
+//! endpoint configured they are also exported to a collector.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::server::error::McpServerError;
+
+/// Name spans are reported under
+const TRACER_NAME: &str = "rust-mcp-server";
+
+/// Create a provider that batches spans to the OTLP collector at `endpoint`
+pub fn otlp_tracer_provider(endpoint: &str) -> Result<TracerProvider, McpServerError> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| McpServerError::Config(format!("Invalid OTLP endpoint {endpoint}: {e}")))?;
+
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .build())
+}
+
+/// Layer forwarding `tracing` spans to `provider`
+pub fn layer<S>(provider: &TracerProvider) -> OpenTelemetryLayer<S, Tracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME))
+}
@@ -0,0 +1,136 @@
+// Per-tool circuit breaker
+// After a run of consecutive execution failures a tool is disabled for a cooldown
+// period, so a tool that is failing catastrophically (e.g. disk full) stops
+// accepting calls until it has had time to recover.
+
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::server::error::ToolError;
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Tracks consecutive failures per tool and temporarily disables failing tools
+#[derive(Clone)]
+pub struct ToolCircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    states: Arc<DashMap<String, BreakerState>>,
+}
+
+impl ToolCircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            states: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Fail fast if the tool is disabled; re-enable it once the cooldown has elapsed
+    pub fn check(&self, tool: &str) -> Result<(), ToolError> {
+        let Some(mut state) = self.states.get_mut(tool) else {
+            return Ok(());
+        };
+
+        match state.open_until {
+            Some(until) if Instant::now() < until => {
+                let remaining = until.saturating_duration_since(Instant::now());
+                Err(ToolError::ExecutionError(format!(
+                    "Tool '{tool}' is temporarily disabled after {} consecutive failures; retry in {}s",
+                    self.failure_threshold,
+                    remaining.as_secs().max(1)
+                )))
+            }
+            Some(_) => {
+                *state = BreakerState::default();
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Record the outcome of a call.
+    ///
+    /// Only execution and serialization errors count as failures; invalid input
+    /// is the caller's fault and does not trip the breaker. Returns true when
+    /// this call disabled the tool.
+    pub fn record(&self, tool: &str, result: &Result<serde_json::Value, ToolError>) -> bool {
+        match result {
+            Ok(_) => {
+                self.states.remove(tool);
+                false
+            }
+            Err(ToolError::ExecutionError(_)) | Err(ToolError::SerializationError(_)) => {
+                let mut state = self.states.entry(tool.to_string()).or_default();
+                state.consecutive_failures += 1;
+
+                if state.consecutive_failures >= self.failure_threshold {
+                    state.consecutive_failures = 0;
+                    state.open_until = Some(Instant::now() + self.cooldown);
+                    true
+                } else {
+                    false
+                }
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure() -> Result<serde_json::Value, ToolError> {
+        Err(ToolError::ExecutionError(
+            "No space left on device".to_string(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_breaker_disables_then_recovers() {
+        let breaker = ToolCircuitBreaker::new(3, Duration::from_millis(50));
+
+        assert!(!breaker.record("file_search", &failure()));
+        assert!(!breaker.record("file_search", &failure()));
+        assert!(breaker.check("file_search").is_ok());
+
+        // Third consecutive failure trips the breaker
+        assert!(breaker.record("file_search", &failure()));
+        assert!(matches!(
+            breaker.check("file_search"),
+            Err(ToolError::ExecutionError(_))
+        ));
+
+        // Other tools are unaffected
+        assert!(breaker.check("detect_encoding").is_ok());
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(breaker.check("file_search").is_ok());
+
+        // Recovery starts a fresh failure count
+        assert!(!breaker.record("file_search", &failure()));
+        assert!(breaker.check("file_search").is_ok());
+    }
+
+    #[test]
+    fn test_breaker_ignores_user_errors_and_resets_on_success() {
+        let breaker = ToolCircuitBreaker::new(2, Duration::from_secs(60));
+
+        assert!(!breaker.record("tool", &failure()));
+        assert!(!breaker.record("tool", &Ok(serde_json::json!({}))));
+        assert!(!breaker.record("tool", &failure()));
+
+        for _ in 0..5 {
+            let invalid = Err(ToolError::InvalidInput("bad pattern".to_string()));
+            assert!(!breaker.record("tool", &invalid));
+        }
+        assert!(breaker.check("tool").is_ok());
+    }
+}
@@ -0,0 +1,384 @@
+// Encoding and BOM detection tool implementation with schemars
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::io::AsyncReadExt;
+
+use super::McpTool;
+use crate::server::error::ToolError;
+use crate::shared::config::SecurityConfig;
+
+const DEFAULT_SAMPLE_BYTES: u32 = 8192;
+/// Upper bound on the sample, whatever the caller asks for
+const MAX_SAMPLE_BYTES: u64 = 1024 * 1024;
+
+/// Input parameters for encoding detection tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DetectEncodingInput {
+    /// Path of the file to inspect
+    #[schemars(description = "Path of the file whose encoding should be detected")]
+    pub path: PathBuf,
+
+    /// Number of leading bytes to examine
+    #[schemars(
+        description = "Number of bytes from the start of the file to examine",
+        range(min = 4, max = 1048576)
+    )]
+    pub sample_bytes: Option<u32>,
+}
+
+/// Output from encoding detection tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DetectEncodingOutput {
+    /// Path of the inspected file
+    #[schemars(description = "Path of the inspected file")]
+    pub path: String,
+
+    /// Detected or guessed encoding
+    #[schemars(
+        description = "Detected encoding: ASCII, UTF-8, UTF-16LE, UTF-16BE, UTF-32LE, UTF-32BE or unknown"
+    )]
+    pub encoding: String,
+
+    /// Byte order mark found at the start of the file, if any
+    #[schemars(description = "Encoding named by the byte order mark, if one is present")]
+    pub bom: Option<String>,
+
+    /// Whether the encoding came from a BOM rather than a heuristic guess
+    #[schemars(description = "False when the encoding was guessed from the content")]
+    pub from_bom: bool,
+
+    /// Line ending style
+    #[schemars(description = "Line ending style: LF, CRLF, CR, mixed or none")]
+    pub line_endings: String,
+
+    /// Number of bytes examined
+    #[schemars(description = "Number of bytes read from the start of the file")]
+    pub bytes_examined: u64,
+}
+
+/// Encoding detection tool implementation
+pub struct DetectEncodingTool {
+    allowed_extensions: Vec<String>,
+    max_file_size_bytes: u64,
+}
+
+impl DetectEncodingTool {
+    pub fn new(security: &SecurityConfig) -> Self {
+        Self {
+            allowed_extensions: security.allowed_file_extensions.clone(),
+            max_file_size_bytes: security.max_file_size_bytes,
+        }
+    }
+
+    fn check_allowed(&self, path: &std::path::Path) -> Result<(), ToolError> {
+        if self.allowed_extensions.is_empty() {
+            return Ok(());
+        }
+
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if self.allowed_extensions.iter().any(|e| e == extension) {
+            Ok(())
+        } else {
+            Err(ToolError::InvalidInput(format!(
+                "File extension not allowed: {}",
+                path.display()
+            )))
+        }
+    }
+}
+
+#[async_trait]
+impl McpTool for DetectEncodingTool {
+    type Input = DetectEncodingInput;
+    type Output = DetectEncodingOutput;
+
+    fn name(&self) -> &'static str {
+        "detect_encoding"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detect a file's text encoding, byte order mark (UTF-8/16/32) and line ending style by inspecting its leading bytes."
+    }
+
+    async fn execute(&self, input: Self::Input) -> Result<Self::Output, ToolError> {
+        self.check_allowed(&input.path)?;
+
+        let sample_bytes = (input.sample_bytes.unwrap_or(DEFAULT_SAMPLE_BYTES).max(4) as u64)
+            .min(self.max_file_size_bytes)
+            .min(MAX_SAMPLE_BYTES);
+        let file = tokio::fs::File::open(&input.path).await.map_err(|e| {
+            ToolError::InvalidInput(format!("Cannot open {}: {e}", input.path.display()))
+        })?;
+
+        let mut sample = Vec::with_capacity(sample_bytes as usize);
+        file.take(sample_bytes)
+            .read_to_end(&mut sample)
+            .await
+            .map_err(|e| ToolError::ExecutionError(format!("Failed to read file: {e}")))?;
+
+        let detection = detect(&sample);
+
+        Ok(DetectEncodingOutput {
+            path: input.path.to_string_lossy().to_string(),
+            encoding: detection.encoding.name().to_string(),
+            bom: detection
+                .from_bom
+                .then(|| detection.encoding.name().to_string()),
+            from_bom: detection.from_bom,
+            line_endings: line_endings(&sample[detection.bom_len..], detection.encoding)
+                .to_string(),
+            bytes_examined: sample.len() as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Encoding {
+    Ascii,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+    Unknown,
+}
+
+impl Encoding {
+    fn name(self) -> &'static str {
+        match self {
+            Encoding::Ascii => "ASCII",
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Utf32Le => "UTF-32LE",
+            Encoding::Utf32Be => "UTF-32BE",
+            Encoding::Unknown => "unknown",
+        }
+    }
+}
+
+struct Detection {
+    encoding: Encoding,
+    from_bom: bool,
+    bom_len: usize,
+}
+
+fn detect(bytes: &[u8]) -> Detection {
+    // UTF-32 BOMs must be checked before UTF-16, since FF FE prefixes both
+    let boms: [(&[u8], Encoding); 5] = [
+        (&[0xFF, 0xFE, 0x00, 0x00], Encoding::Utf32Le),
+        (&[0x00, 0x00, 0xFE, 0xFF], Encoding::Utf32Be),
+        (&[0xEF, 0xBB, 0xBF], Encoding::Utf8),
+        (&[0xFF, 0xFE], Encoding::Utf16Le),
+        (&[0xFE, 0xFF], Encoding::Utf16Be),
+    ];
+
+    for (bom, encoding) in boms {
+        if bytes.starts_with(bom) {
+            return Detection {
+                encoding,
+                from_bom: true,
+                bom_len: bom.len(),
+            };
+        }
+    }
+
+    Detection {
+        encoding: guess_encoding(bytes),
+        from_bom: false,
+        bom_len: 0,
+    }
+}
+
+fn guess_encoding(bytes: &[u8]) -> Encoding {
+    if bytes.is_ascii() && !bytes.contains(&0) {
+        return Encoding::Ascii;
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(_) if !bytes.contains(&0) => return Encoding::Utf8,
+        // A multi-byte character cut off by the sample boundary is still UTF-8
+        Err(e) if e.error_len().is_none() && !bytes[..e.valid_up_to()].contains(&0) => {
+            return Encoding::Utf8;
+        }
+        _ => {}
+    }
+
+    // Mostly-ASCII UTF-16 text has a zero in every other byte
+    let pairs = bytes.len() / 2;
+    if pairs > 0 {
+        let even_zeros = bytes.iter().step_by(2).filter(|b| **b == 0).count();
+        let odd_zeros = bytes.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+
+        if odd_zeros * 2 > pairs && even_zeros * 10 < pairs {
+            return Encoding::Utf16Le;
+        }
+        if even_zeros * 2 > pairs && odd_zeros * 10 < pairs {
+            return Encoding::Utf16Be;
+        }
+    }
+
+    Encoding::Unknown
+}
+
+/// Decode the sample into code units so CR/LF can be found in any encoding
+fn code_units(bytes: &[u8], encoding: Encoding) -> Vec<u32> {
+    match encoding {
+        Encoding::Utf16Le => bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]) as u32)
+            .collect(),
+        Encoding::Utf16Be => bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]) as u32)
+            .collect(),
+        Encoding::Utf32Le => bytes
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+        Encoding::Utf32Be => bytes
+            .chunks_exact(4)
+            .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+        _ => bytes.iter().map(|b| *b as u32).collect(),
+    }
+}
+
+fn line_endings(bytes: &[u8], encoding: Encoding) -> &'static str {
+    const CR: u32 = 0x0D;
+    const LF: u32 = 0x0A;
+
+    let units = code_units(bytes, encoding);
+    let (mut crlf, mut lf, mut cr) = (0usize, 0usize, 0usize);
+    let mut i = 0;
+    while i < units.len() {
+        match units[i] {
+            CR if units.get(i + 1) == Some(&LF) => {
+                crlf += 1;
+                i += 1;
+            }
+            CR => cr += 1,
+            LF => lf += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    match (crlf > 0, lf > 0, cr > 0) {
+        (false, false, false) => "none",
+        (true, false, false) => "CRLF",
+        (false, true, false) => "LF",
+        (false, false, true) => "CR",
+        _ => "mixed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::Config;
+    use tempfile::TempDir;
+
+    fn tool() -> DetectEncodingTool {
+        DetectEncodingTool::new(&Config::default().security)
+    }
+
+    async fn detect_file(dir: &TempDir, name: &str, contents: &[u8]) -> DetectEncodingOutput {
+        let path = dir.path().join(name);
+        tokio::fs::write(&path, contents).await.unwrap();
+
+        tool()
+            .execute(DetectEncodingInput {
+                path,
+                sample_bytes: None,
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_detect_utf8_bom() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut contents = vec![0xEF, 0xBB, 0xBF];
+        contents.extend_from_slice("héllo\r\nwörld\r\n".as_bytes());
+
+        let result = detect_file(&temp_dir, "bom.txt", &contents).await;
+
+        assert_eq!(result.encoding, "UTF-8");
+        assert_eq!(result.bom.as_deref(), Some("UTF-8"));
+        assert!(result.from_bom);
+        assert_eq!(result.line_endings, "CRLF");
+    }
+
+    #[tokio::test]
+    async fn test_detect_utf16le() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut contents = vec![0xFF, 0xFE];
+        for unit in "line one\nline two\n".encode_utf16() {
+            contents.extend_from_slice(&unit.to_le_bytes());
+        }
+
+        let result = detect_file(&temp_dir, "wide.txt", &contents).await;
+
+        assert_eq!(result.encoding, "UTF-16LE");
+        assert_eq!(result.bom.as_deref(), Some("UTF-16LE"));
+        assert_eq!(result.line_endings, "LF");
+
+        // Without a BOM the zero-byte heuristic should still find UTF-16LE
+        let result = detect_file(&temp_dir, "wide.log", &contents[2..]).await;
+        assert_eq!(result.encoding, "UTF-16LE");
+        assert!(!result.from_bom);
+    }
+
+    #[tokio::test]
+    async fn test_detect_plain_ascii() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = detect_file(&temp_dir, "plain.md", b"# Title\nbody text\n").await;
+
+        assert_eq!(result.encoding, "ASCII");
+        assert_eq!(result.bom, None);
+        assert!(!result.from_bom);
+        assert_eq!(result.line_endings, "LF");
+        assert_eq!(result.bytes_examined, 18);
+    }
+
+    #[tokio::test]
+    async fn test_detect_clamps_oversized_sample() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("large.txt");
+        tokio::fs::write(&path, vec![b'a'; 2 * 1024 * 1024])
+            .await
+            .unwrap();
+
+        let result = tool()
+            .execute(DetectEncodingInput {
+                path,
+                sample_bytes: Some(u32::MAX),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.encoding, "ASCII");
+        assert_eq!(result.bytes_examined, MAX_SAMPLE_BYTES);
+    }
+
+    #[tokio::test]
+    async fn test_detect_rejects_disallowed_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("binary.exe");
+        tokio::fs::write(&path, b"MZ").await.unwrap();
+
+        let result = tool()
+            .execute(DetectEncodingInput {
+                path,
+                sample_bytes: None,
+            })
+            .await;
+
+        assert!(matches!(result, Err(ToolError::InvalidInput(_))));
+    }
+}
@@ -2,15 +2,19 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt};
 use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use tokio::fs;
-use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 
 use super::McpTool;
 use crate::server::error::ToolError;
+use crate::shared::config::ResourceLimitsConfig;
 
 /// Input parameters for file search tool
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -48,6 +52,35 @@ pub struct FileSearchInput {
         default = "default_case_sensitive"
     )]
     pub case_sensitive: Option<bool>,
+
+    /// Include the matched substring in each result
+    #[schemars(
+        description = "Whether each match should include the exact text matched by the pattern",
+        default = "default_include_matched_text"
+    )]
+    pub include_matched_text: Option<bool>,
+
+    /// Maximum number of matches taken from files in any single directory
+    #[schemars(
+        description = "Maximum number of matches from the files directly inside any one directory, so a single crowded directory cannot use up max_results. Subdirectories have their own allowance. Unlimited if omitted.",
+        range(min = 1, max = 1000)
+    )]
+    pub max_results_per_directory: Option<u32>,
+
+    /// Skip files excluded by .gitignore and .ignore files
+    #[schemars(
+        description = "Whether files and directories excluded by .gitignore (and .ignore) files should be skipped",
+        default = "default_respect_gitignore"
+    )]
+    pub respect_gitignore: Option<bool>,
+
+    /// Number of lines of context to include before and after each match
+    #[schemars(
+        description = "Number of lines before and after each match to include as context. Each match gets its full context, even when it overlaps another match's.",
+        range(min = 0, max = 100),
+        default = "default_context_lines"
+    )]
+    pub context_lines: Option<u32>,
 }
 
 /// A single file match result
@@ -65,13 +98,36 @@ pub struct FileMatch {
     #[schemars(description = "The full line containing the match")]
     pub line_content: String,
 
-    /// Column position of the match start (0-based)
-    #[schemars(description = "Column position where the match starts")]
+    /// Column position of the match start (0-based, in characters)
+    #[schemars(description = "Character (not byte) offset within the line where the match starts")]
     pub column_start: u32,
 
-    /// Column position of the match end (0-based)
-    #[schemars(description = "Column position where the match ends")]
+    /// Column position of the match end (0-based, exclusive, in characters)
+    #[schemars(
+        description = "Character (not byte) offset within the line where the match ends (exclusive)"
+    )]
     pub column_end: u32,
+
+    /// Byte offset of the match start from the beginning of the file
+    #[schemars(
+        description = "Absolute byte (not character) offset of the match start from the beginning of the file"
+    )]
+    pub byte_offset: u64,
+
+    /// The exact text matched by the pattern
+    #[schemars(description = "The substring of the line matched by the pattern")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_text: Option<String>,
+
+    /// Lines preceding the matching line, oldest first
+    #[schemars(description = "Up to context_lines lines before the matching line, oldest first")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<String>,
+
+    /// Lines following the matching line
+    #[schemars(description = "Up to context_lines lines after the matching line")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
 }
 
 /// Output from file search tool
@@ -106,8 +162,36 @@ fn default_case_sensitive() -> Option<bool> {
     Some(false)
 }
 
+fn default_include_matched_text() -> Option<bool> {
+    Some(true)
+}
+
+fn default_respect_gitignore() -> Option<bool> {
+    Some(true)
+}
+
+fn default_context_lines() -> Option<u32> {
+    Some(0)
+}
+
+/// Bytes inspected for a NUL byte when deciding whether a file is binary
+const BINARY_SNIFF_BYTES: u64 = 8 * 1024;
+
 /// File search tool implementation
-pub struct FileSearchTool;
+#[derive(Default)]
+pub struct FileSearchTool {
+    /// Files at least this large are scanned through a memory map (0 disables)
+    #[cfg_attr(not(feature = "mmap"), allow(dead_code))]
+    mmap_threshold_bytes: u64,
+}
+
+impl FileSearchTool {
+    pub fn new(limits: &ResourceLimitsConfig) -> Self {
+        Self {
+            mmap_threshold_bytes: limits.file_search_mmap_threshold_bytes,
+        }
+    }
+}
 
 #[async_trait]
 impl McpTool for FileSearchTool {
@@ -119,7 +203,19 @@ impl McpTool for FileSearchTool {
     }
 
     fn description(&self) -> &'static str {
-        "Search for text patterns in files using regular expressions. Supports filtering by file extensions and case-sensitive/insensitive matching."
+        "Search for text patterns in files using regular expressions. Supports filtering by file extensions and case-sensitive/insensitive matching. Files excluded by .gitignore and binary files are skipped."
+    }
+
+    fn examples(&self) -> Vec<serde_json::Value> {
+        vec![
+            serde_json::json!({ "pattern": "TODO", "extensions": ["rs"] }),
+            serde_json::json!({
+                "pattern": "fn\\s+main",
+                "directory": "src",
+                "max_results": 10,
+                "case_sensitive": true
+            }),
+        ]
     }
 
     async fn execute(&self, input: Self::Input) -> Result<Self::Output, ToolError> {
@@ -128,6 +224,8 @@ impl McpTool for FileSearchTool {
         // Set defaults
         let max_results = input.max_results.unwrap_or(100);
         let case_sensitive = input.case_sensitive.unwrap_or(false);
+        let include_matched_text = input.include_matched_text.unwrap_or(true);
+        let respect_gitignore = input.respect_gitignore.unwrap_or(true);
         let search_dir = input.directory.unwrap_or_else(|| PathBuf::from("."));
 
         // Validate directory exists
@@ -153,20 +251,26 @@ impl McpTool for FileSearchTool {
         let mut total_files_searched = 0;
         let mut truncated = false;
 
-        let search_result = self
-            .search_directory(
-                &search_dir,
-                &pattern,
-                &input.extensions,
-                max_results,
-                &mut matches,
-                &mut total_files_searched,
-            )
-            .await;
+        let options = SearchOptions {
+            pattern: &pattern,
+            max_results,
+            max_results_per_directory: input.max_results_per_directory,
+            include_matched_text,
+            context_lines: input.context_lines.unwrap_or(0),
+        };
 
-        if let Err(e) = search_result {
-            return Err(ToolError::ExecutionError(format!("Search failed: {e}")));
-        }
+        let files = collect_files(search_dir, input.extensions.clone(), respect_gitignore)
+            .await
+            .map_err(|e| ToolError::ExecutionError(format!("Search failed: {e}")))?;
+
+        self.search_files(
+            &files,
+            &options,
+            search_concurrency(),
+            &mut matches,
+            &mut total_files_searched,
+        )
+        .await;
 
         if matches.len() >= max_results as usize {
             truncated = true;
@@ -184,94 +288,386 @@ impl McpTool for FileSearchTool {
     }
 }
 
+/// Settings shared by every level of a single search
+struct SearchOptions<'a> {
+    pattern: &'a Regex,
+    max_results: u32,
+    max_results_per_directory: Option<u32>,
+    include_matched_text: bool,
+    context_lines: u32,
+}
+
 impl FileSearchTool {
-    async fn search_directory(
+    /// Search up to `concurrency` of `files` at a time until `max_results`
+    /// matches are found.
+    ///
+    /// Results are merged in file order, so the matches, `total_files` and
+    /// per-directory allowances are the same as searching one file at a time.
+    async fn search_files(
         &self,
-        dir: &PathBuf,
-        pattern: &Regex,
-        extensions: &Option<Vec<String>>,
-        max_results: u32,
+        files: &[PathBuf],
+        options: &SearchOptions<'_>,
+        concurrency: usize,
         matches: &mut Vec<FileMatch>,
         total_files: &mut u32,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut entries = fs::read_dir(dir).await?;
+    ) {
+        let max_results = options.max_results as usize;
+        let per_file_limit = options
+            .max_results_per_directory
+            .map_or(max_results, |cap| max_results.min(cap as usize));
+
+        let mut searches = stream::iter(files.iter().cloned().enumerate())
+            .map(|(index, path)| async move {
+                if is_binary(&path).await {
+                    return (index, None);
+                }
+                let mut found = Vec::new();
+                let result = self
+                    .search_file(&path, options, &mut found, per_file_limit)
+                    .await;
+                (index, Some((found, result)))
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        // Finished searches waiting for an earlier file to complete
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        // Matches taken from files directly in each directory
+        let mut dir_matches: HashMap<&Path, usize> = HashMap::new();
 
-        while let Some(entry) = entries.next_entry().await? {
-            // Check if we've hit the limit
-            if matches.len() >= max_results as usize {
+        while matches.len() < max_results {
+            let Some((index, searched)) = searches.next().await else {
                 break;
-            }
+            };
+            pending.insert(index, searched);
 
-            let path = entry.path();
-
-            if path.is_dir() {
-                // Recursively search subdirectories
-                Box::pin(self.search_directory(
-                    &path,
-                    pattern,
-                    extensions,
-                    max_results,
-                    matches,
-                    total_files,
-                ))
-                .await?;
-            } else if path.is_file() {
-                // Check file extension filter
-                if let Some(exts) = extensions {
-                    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                        if !exts.iter().any(|e| e == ext) {
-                            continue;
-                        }
-                    } else {
-                        continue; // Skip files without extensions if extensions filter is provided
-                    }
+            while matches.len() < max_results {
+                let Some(searched) = pending.remove(&next) else {
+                    break;
+                };
+                let path = &files[next];
+                next += 1;
+
+                // Skip remaining files once this directory's allowance is used
+                let dir = path.parent().unwrap_or(Path::new(""));
+                let dir_count = dir_matches.entry(dir).or_default();
+                let dir_remaining = match options.max_results_per_directory {
+                    Some(cap) => (cap as usize).saturating_sub(*dir_count),
+                    None => usize::MAX,
+                };
+                if dir_remaining == 0 {
+                    continue;
                 }
 
+                // Binary files are not searched
+                let Some((found, result)) = searched else {
+                    continue;
+                };
                 *total_files += 1;
 
-                // Search file contents
-                if let Err(e) = self.search_file(&path, pattern, matches, max_results).await {
-                    eprintln!("Error searching file {}: {}", path.display(), e);
-                    continue;
+                let take = dir_remaining.min(max_results - matches.len());
+                *dir_count += found.len().min(take);
+                matches.extend(found.into_iter().take(take));
+
+                if let Err(e) = result {
+                    tracing::warn!("Error searching file {}: {}", path.display(), e);
                 }
             }
         }
-
-        Ok(())
     }
 
     async fn search_file(
         &self,
         file_path: &PathBuf,
-        pattern: &Regex,
+        options: &SearchOptions<'_>,
         matches: &mut Vec<FileMatch>,
-        max_results: u32,
+        limit: usize,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let file = fs::File::open(file_path).await?;
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
-        let mut line_number = 1;
 
-        while let Some(line) = lines.next_line().await? {
-            // Check if we've hit the limit
-            if matches.len() >= max_results as usize {
+        #[cfg(feature = "mmap")]
+        if self.mmap_threshold_bytes > 0
+            && file.metadata().await?.len() >= self.mmap_threshold_bytes
+        {
+            return search_file_mapped(
+                file.into_std().await,
+                file_path.clone(),
+                options,
+                matches,
+                limit,
+            )
+            .await;
+        }
+
+        let mut reader = BufReader::new(file);
+        let mut buf = String::new();
+        let mut matcher = LineMatcher::new(file_path, options, matches);
+
+        while !matcher.is_done(matches, limit) {
+            buf.clear();
+            let read = reader.read_line(&mut buf).await?;
+            if read == 0 {
                 break;
             }
+            // Strip the line ending the same way `AsyncBufReadExt::lines` does
+            let line = match buf.strip_suffix('\n') {
+                Some(line) => line.strip_suffix('\r').unwrap_or(line),
+                None => &buf,
+            };
+
+            matcher.push_line(line, read, matches, limit);
+        }
+
+        Ok(())
+    }
+}
 
-            if let Some(mat) = pattern.find(&line) {
-                matches.push(FileMatch {
-                    file_path: file_path.to_string_lossy().to_string(),
-                    line_number,
-                    line_content: line.clone(),
-                    column_start: mat.start() as u32,
-                    column_end: mat.end() as u32,
-                });
+/// List the files under `dir` that pass the extension filter.
+///
+/// Hidden files are included as before; when `respect_gitignore` is set,
+/// paths excluded by .gitignore and .ignore files are left out even outside a
+/// git repository.
+async fn collect_files(
+    dir: PathBuf,
+    extensions: Option<Vec<String>>,
+    respect_gitignore: bool,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(move || {
+        let walker = ignore::WalkBuilder::new(&dir)
+            .standard_filters(respect_gitignore)
+            .hidden(false)
+            .require_git(false)
+            .follow_links(true)
+            .build();
+
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    tracing::warn!("Error walking {}: {}", dir.display(), e);
+                    continue;
+                }
+            };
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
             }
 
-            line_number += 1;
+            // Skip files without extensions if an extensions filter is provided
+            if let Some(exts) = &extensions {
+                let ext = entry.path().extension().and_then(|e| e.to_str());
+                if !ext.is_some_and(|ext| exts.iter().any(|e| e == ext)) {
+                    continue;
+                }
+            }
+
+            files.push(entry.into_path());
         }
+        files
+    })
+    .await
+    .map_err(Into::into)
+}
 
-        Ok(())
+/// Number of files searched at the same time
+fn search_concurrency() -> usize {
+    std::thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Whether the start of the file contains a NUL byte, as text never does.
+///
+/// Unreadable files are not treated as binary so the search reports the error.
+async fn is_binary(path: &Path) -> bool {
+    let Ok(file) = fs::File::open(path).await else {
+        return false;
+    };
+    let mut head = Vec::with_capacity(BINARY_SNIFF_BYTES as usize);
+    if file
+        .take(BINARY_SNIFF_BYTES)
+        .read_to_end(&mut head)
+        .await
+        .is_err()
+    {
+        return false;
+    }
+    head.contains(&0)
+}
+
+/// Matches a file line by line, attaching context to each match
+struct LineMatcher<'a> {
+    file_path: &'a Path,
+    options: &'a SearchOptions<'a>,
+    line_number: u32,
+    line_offset: u64,
+    /// The last `context_lines` lines, oldest first
+    before: VecDeque<String>,
+    /// First match from this file still collecting its `after` lines
+    pending: usize,
+}
+
+impl<'a> LineMatcher<'a> {
+    /// Start a file whose matches are appended after the existing `matches`
+    fn new(file_path: &'a Path, options: &'a SearchOptions<'a>, matches: &[FileMatch]) -> Self {
+        Self {
+            file_path,
+            options,
+            line_number: 1,
+            line_offset: 0,
+            before: VecDeque::new(),
+            pending: matches.len(),
+        }
+    }
+
+    /// Whether the limit is reached and every match has its `after` context
+    fn is_done(&mut self, matches: &[FileMatch], limit: usize) -> bool {
+        let context = self.options.context_lines as usize;
+        while matches
+            .get(self.pending)
+            .is_some_and(|m| m.after.len() >= context)
+        {
+            self.pending += 1;
+        }
+        matches.len() >= limit && self.pending == matches.len()
+    }
+
+    /// Match the next line, `len` bytes long including its line ending
+    fn push_line(&mut self, line: &str, len: usize, matches: &mut Vec<FileMatch>, limit: usize) {
+        let context = self.options.context_lines as usize;
+        for m in &mut matches[self.pending..] {
+            if m.after.len() < context {
+                m.after.push(line.to_string());
+            }
+        }
+
+        if matches.len() < limit {
+            let before: Vec<String> = self.before.iter().cloned().collect();
+            let found = match_line(
+                self.file_path,
+                line,
+                self.line_number,
+                self.line_offset,
+                self.options,
+            );
+            matches.extend(found.take(limit - matches.len()).map(|mut m| {
+                m.before = before.clone();
+                m
+            }));
+        }
+
+        if context > 0 {
+            if self.before.len() == context {
+                self.before.pop_front();
+            }
+            self.before.push_back(line.to_string());
+        }
+        self.line_number += 1;
+        self.line_offset += len as u64;
+    }
+}
+
+/// Match every occurrence in a single line starting `line_offset` bytes into
+/// the file, reporting columns as character offsets
+fn match_line<'a>(
+    file_path: &Path,
+    line: &'a str,
+    line_number: u32,
+    line_offset: u64,
+    options: &'a SearchOptions<'a>,
+) -> impl Iterator<Item = FileMatch> + 'a {
+    let file_path = file_path.to_string_lossy().to_string();
+    // Characters before `counted_to`, so each match only counts the gap since the last
+    let mut chars_before = 0;
+    let mut counted_to = 0;
+
+    options.pattern.find_iter(line).map(move |mat| {
+        // Regex offsets are bytes; report columns as character offsets
+        chars_before += line[counted_to..mat.start()].chars().count();
+        counted_to = mat.start();
+        let column_end = chars_before + mat.as_str().chars().count();
+
+        FileMatch {
+            file_path: file_path.clone(),
+            line_number,
+            line_content: line.to_string(),
+            column_start: chars_before as u32,
+            column_end: column_end as u32,
+            byte_offset: line_offset + mat.start() as u64,
+            matched_text: options
+                .include_matched_text
+                .then(|| mat.as_str().to_string()),
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    })
+}
+
+/// Scan a large file through a memory map on the blocking pool.
+///
+/// Lines are split exactly as `AsyncBufReadExt::lines` splits them, so the
+/// matches are the same as the reader path's, including stopping at the first
+/// line that is not valid UTF-8.
+#[cfg(feature = "mmap")]
+async fn search_file_mapped(
+    file: std::fs::File,
+    file_path: PathBuf,
+    options: &SearchOptions<'_>,
+    matches: &mut Vec<FileMatch>,
+    limit: usize,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let pattern = options.pattern.clone();
+    let include_matched_text = options.include_matched_text;
+    let context_lines = options.context_lines;
+    let remaining = limit.saturating_sub(matches.len());
+
+    let (found, error) = tokio::task::spawn_blocking(move || {
+        let options = SearchOptions {
+            pattern: &pattern,
+            max_results: 0,
+            max_results_per_directory: None,
+            include_matched_text,
+            context_lines,
+        };
+        let mut found = Vec::new();
+        let mut matcher = LineMatcher::new(&file_path, &options, &found);
+
+        // Safety: the map is read-only and dropped before returning; a file
+        // truncated while mapped is the same hazard any mmap reader accepts.
+        let map = match unsafe { memmap2::Mmap::map(&file) } {
+            Ok(map) => map,
+            Err(e) => return (found, Some(e.to_string())),
+        };
+
+        let mut rest: &[u8] = &map;
+        while !rest.is_empty() && !matcher.is_done(&found, remaining) {
+            let len = rest.len();
+            let line = match rest.iter().position(|b| *b == b'\n') {
+                Some(end) => {
+                    let line = &rest[..end];
+                    rest = &rest[end + 1..];
+                    line.strip_suffix(b"\r").unwrap_or(line)
+                }
+                None => std::mem::take(&mut rest),
+            };
+
+            let line = match std::str::from_utf8(line) {
+                Ok(line) => line,
+                Err(_) => {
+                    let error = "stream did not contain valid UTF-8".to_string();
+                    return (found, Some(error));
+                }
+            };
+
+            matcher.push_line(line, len - rest.len(), &mut found, remaining);
+        }
+
+        (found, None)
+    })
+    .await?;
+
+    matches.extend(found);
+    match error {
+        Some(error) => Err(error.into()),
+        None => Ok(()),
     }
 }
 
@@ -281,6 +677,22 @@ mod tests {
     use tempfile::TempDir;
     use tokio::fs;
 
+    #[test]
+    fn test_file_search_examples_match_input_schema() {
+        let tool = FileSearchTool::default();
+        let examples = tool.examples();
+        assert!(!examples.is_empty());
+
+        let schema = jsonschema::JSONSchema::compile(&tool.input_schema()).unwrap();
+        for example in &examples {
+            assert!(
+                schema.is_valid(example),
+                "{example} does not match the schema"
+            );
+            assert!(serde_json::from_value::<FileSearchInput>(example.clone()).is_ok());
+        }
+    }
+
     #[tokio::test]
     async fn test_file_search_basic() {
         let temp_dir = TempDir::new().unwrap();
@@ -290,13 +702,17 @@ mod tests {
             .await
             .unwrap();
 
-        let tool = FileSearchTool;
+        let tool = FileSearchTool::default();
         let input = FileSearchInput {
             pattern: "Error.*".to_string(),
             directory: Some(temp_dir.path().to_path_buf()),
             max_results: Some(10),
             extensions: None,
             case_sensitive: Some(false),
+            include_matched_text: None,
+            max_results_per_directory: None,
+            respect_gitignore: None,
+            context_lines: None,
         };
 
         let result = tool.execute(input).await.unwrap();
@@ -311,6 +727,298 @@ mod tests {
         assert!(!result.truncated);
     }
 
+    #[tokio::test]
+    async fn test_file_search_matched_text_multibyte() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("unicode.txt");
+
+        fs::write(&file_path, "héllo wörld: naïve café\n")
+            .await
+            .unwrap();
+
+        let tool = FileSearchTool::default();
+        let input = FileSearchInput {
+            pattern: "naïve".to_string(),
+            directory: Some(temp_dir.path().to_path_buf()),
+            max_results: Some(10),
+            extensions: None,
+            case_sensitive: Some(true),
+            include_matched_text: Some(true),
+            max_results_per_directory: None,
+            respect_gitignore: None,
+            context_lines: None,
+        };
+
+        let result = tool.execute(input).await.unwrap();
+
+        assert_eq!(result.matches.len(), 1);
+        let mat = &result.matches[0];
+        assert_eq!(mat.matched_text.as_deref(), Some("naïve"));
+        assert_eq!(mat.column_start, 13);
+        assert_eq!(mat.column_end, 18);
+
+        // Columns are character offsets, so slicing by chars recovers the match
+        let sliced: String = mat
+            .line_content
+            .chars()
+            .skip(mat.column_start as usize)
+            .take((mat.column_end - mat.column_start) as usize)
+            .collect();
+        assert_eq!(sliced, "naïve");
+    }
+
+    #[tokio::test]
+    async fn test_file_search_per_directory_cap_spreads_results() {
+        let temp_dir = TempDir::new().unwrap();
+        let crowded = temp_dir.path().join("crowded");
+        fs::create_dir(&crowded).await.unwrap();
+        for i in 0..20 {
+            fs::write(crowded.join(format!("f{i}.txt")), "TODO crowded\n")
+                .await
+                .unwrap();
+        }
+        for sibling in ["alpha", "beta"] {
+            let dir = temp_dir.path().join(sibling);
+            fs::create_dir(&dir).await.unwrap();
+            for i in 0..2 {
+                fs::write(dir.join(format!("f{i}.txt")), "TODO sibling\n")
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let tool = FileSearchTool::default();
+        let input = FileSearchInput {
+            pattern: "TODO".to_string(),
+            directory: Some(temp_dir.path().to_path_buf()),
+            max_results: Some(10),
+            extensions: None,
+            case_sensitive: Some(true),
+            include_matched_text: None,
+            max_results_per_directory: Some(3),
+            respect_gitignore: None,
+            context_lines: None,
+        };
+
+        let result = tool.execute(input).await.unwrap();
+
+        let from_dir = |name: &str| {
+            let dir = temp_dir.path().join(name);
+            result
+                .matches
+                .iter()
+                .filter(|m| PathBuf::from(&m.file_path).parent() == Some(dir.as_path()))
+                .count()
+        };
+
+        assert_eq!(from_dir("crowded"), 3);
+        assert_eq!(from_dir("alpha"), 2);
+        assert_eq!(from_dir("beta"), 2);
+        assert_eq!(result.matches.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_file_search_respects_gitignore() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "target/\n")
+            .await
+            .unwrap();
+        let target = temp_dir.path().join("target");
+        fs::create_dir(&target).await.unwrap();
+        fs::write(target.join("build.rs"), "TODO generated\n")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("main.rs"), "// nothing here\n")
+            .await
+            .unwrap();
+
+        let tool = FileSearchTool::default();
+        let input = FileSearchInput {
+            pattern: "TODO".to_string(),
+            directory: Some(temp_dir.path().to_path_buf()),
+            max_results: Some(10),
+            extensions: Some(vec!["rs".to_string()]),
+            case_sensitive: Some(true),
+            include_matched_text: None,
+            max_results_per_directory: None,
+            respect_gitignore: None,
+            context_lines: None,
+        };
+
+        let result = tool.execute(input.clone()).await.unwrap();
+        assert!(result.matches.is_empty());
+        assert_eq!(result.total_files_searched, 1);
+
+        let result = tool
+            .execute(FileSearchInput {
+                respect_gitignore: Some(false),
+                ..input
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.matches[0].file_path.ends_with("build.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_file_search_skips_binary_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("blob.bin"), b"TODO\0\x01\x02\nTODO\n")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "TODO text\n")
+            .await
+            .unwrap();
+
+        let tool = FileSearchTool::default();
+        let input = FileSearchInput {
+            pattern: "TODO".to_string(),
+            directory: Some(temp_dir.path().to_path_buf()),
+            max_results: Some(10),
+            extensions: None,
+            case_sensitive: Some(true),
+            include_matched_text: None,
+            max_results_per_directory: None,
+            respect_gitignore: None,
+            context_lines: None,
+        };
+
+        let result = tool.execute(input).await.unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.matches[0].file_path.ends_with("notes.txt"));
+        assert_eq!(result.total_files_searched, 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_search_matches_sequential() {
+        let temp_dir = TempDir::new().unwrap();
+        for d in 0..8 {
+            let dir = temp_dir.path().join(format!("dir{d}"));
+            fs::create_dir(&dir).await.unwrap();
+            for f in 0..50 {
+                let mut contents = String::new();
+                for line in 0..20 {
+                    if (d + f + line) % 7 == 0 {
+                        contents.push_str(&format!("TODO {d}/{f}/{line}\n"));
+                    } else {
+                        contents.push_str("nothing to see\n");
+                    }
+                }
+                fs::write(dir.join(format!("f{f}.txt")), contents)
+                    .await
+                    .unwrap();
+            }
+        }
+        let files = collect_files(temp_dir.path().to_path_buf(), None, true)
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 400);
+
+        let pattern = Regex::new("TODO").unwrap();
+        let tool = FileSearchTool::default();
+        for (max_results, max_results_per_directory) in [
+            (1000, None),
+            (250, None),
+            (37, None),
+            (1000, Some(30)),
+            (100, Some(9)),
+        ] {
+            let options = SearchOptions {
+                pattern: &pattern,
+                max_results,
+                max_results_per_directory,
+                include_matched_text: true,
+                context_lines: 2,
+            };
+            let search = |concurrency| {
+                let (tool, files, options) = (&tool, &files, &options);
+                async move {
+                    let mut matches = Vec::new();
+                    let mut total_files = 0;
+                    tool.search_files(files, options, concurrency, &mut matches, &mut total_files)
+                        .await;
+                    let matches: Vec<_> = matches
+                        .into_iter()
+                        .map(|m| serde_json::to_value(m).unwrap())
+                        .collect();
+                    (matches, total_files)
+                }
+            };
+
+            let (sequential, sequential_files) = search(1).await;
+            let (concurrent, concurrent_files) = search(16).await;
+
+            assert!(!sequential.is_empty());
+            assert!(sequential.len() <= max_results as usize);
+            assert_eq!(sequential, concurrent);
+            assert_eq!(sequential_files, concurrent_files);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_search_context_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("notes.txt"),
+            "TODO one\ntwo\nTODO three\nfour\nfive TODO",
+        )
+        .await
+        .unwrap();
+
+        let tool = FileSearchTool::default();
+        let input = FileSearchInput {
+            pattern: "TODO".to_string(),
+            directory: Some(temp_dir.path().to_path_buf()),
+            max_results: Some(10),
+            extensions: None,
+            case_sensitive: Some(true),
+            include_matched_text: None,
+            max_results_per_directory: None,
+            respect_gitignore: None,
+            context_lines: Some(1),
+        };
+
+        let context = |matches: &[FileMatch]| {
+            matches
+                .iter()
+                .map(|m| (m.line_number, m.before.clone(), m.after.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let result = tool.execute(input.clone()).await.unwrap();
+        assert_eq!(
+            context(&result.matches),
+            vec![
+                (1, vec![], vec!["two".to_string()]),
+                (3, vec!["two".to_string()], vec!["four".to_string()]),
+                (5, vec!["four".to_string()], vec![]),
+            ]
+        );
+
+        // Overlapping context is repeated for each match
+        let result = tool
+            .execute(FileSearchInput {
+                context_lines: Some(2),
+                ..input.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.matches[1].before, vec!["TODO one", "two"]);
+        assert_eq!(result.matches[1].after, vec!["four", "five TODO"]);
+
+        // The last match still gets its trailing context when max_results is hit
+        let result = tool
+            .execute(FileSearchInput {
+                max_results: Some(1),
+                context_lines: Some(2),
+                ..input
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].after, vec!["two", "TODO three"]);
+    }
+
     #[tokio::test]
     async fn test_file_search_schema_generation() {
         let input_schema = schemars::schema_for!(FileSearchInput);
@@ -324,5 +1032,118 @@ mod tests {
         assert!(input_json["properties"]["pattern"].is_object());
         assert!(output_json["properties"]["matches"].is_object());
         assert!(output_json["properties"]["total_files_searched"].is_object());
+        let file_match = &output_json["definitions"]["FileMatch"]["properties"];
+        assert!(file_match["byte_offset"].is_object());
+        assert!(file_match["column_start"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_file_search_multiple_matches_per_line() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("notes.txt"),
+            "first line\r\nTODO: café, then TODO again\n",
+        )
+        .await
+        .unwrap();
+
+        let tool = FileSearchTool::default();
+        let input = FileSearchInput {
+            pattern: "TODO".to_string(),
+            directory: Some(temp_dir.path().to_path_buf()),
+            max_results: Some(10),
+            extensions: None,
+            case_sensitive: Some(true),
+            include_matched_text: None,
+            max_results_per_directory: None,
+            respect_gitignore: None,
+            context_lines: None,
+        };
+
+        let result = tool.execute(input.clone()).await.unwrap();
+
+        assert_eq!(result.matches.len(), 2);
+        assert!(result.matches.iter().all(|m| m.line_number == 2));
+        // "first line\r\n" is 12 bytes; "é" is one character but two bytes
+        assert_eq!(result.matches[0].byte_offset, 12);
+        assert_eq!(result.matches[0].column_start, 0);
+        assert_eq!(result.matches[1].byte_offset, 12 + 18);
+        assert_eq!(result.matches[1].column_start, 17);
+        assert_eq!(result.matches[1].column_end, 21);
+
+        // max_results applies to occurrences, not lines
+        let result = tool
+            .execute(FileSearchInput {
+                max_results: Some(1),
+                ..input
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.truncated);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[tokio::test]
+    async fn test_mmap_and_reader_paths_match() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut contents = String::new();
+        for i in 0..1_200 {
+            match i % 4 {
+                0 => contents.push_str(&format!("line {i}: Error in café\r\n")),
+                1 => contents.push_str(&format!("line {i}: ok\n")),
+                2 => contents.push_str("\n"),
+                _ => contents.push_str(&format!("ERROR {i} naïve\n")),
+            }
+        }
+        contents.push_str("final error without newline\r");
+        fs::write(temp_dir.path().join("large.log"), &contents)
+            .await
+            .unwrap();
+
+        // Matches before invalid UTF-8 are kept, the rest of the file is skipped
+        let mut invalid = b"error before\n".to_vec();
+        invalid.extend_from_slice(&[0xff, 0xfe, b'\n']);
+        invalid.extend_from_slice(b"error after\n");
+        fs::write(temp_dir.path().join("invalid.log"), invalid)
+            .await
+            .unwrap();
+
+        let input = FileSearchInput {
+            pattern: "error".to_string(),
+            directory: Some(temp_dir.path().to_path_buf()),
+            max_results: Some(1000),
+            extensions: None,
+            case_sensitive: Some(false),
+            include_matched_text: Some(true),
+            max_results_per_directory: None,
+            respect_gitignore: None,
+            context_lines: Some(2),
+        };
+
+        let reader = FileSearchTool::default();
+        let mapped = FileSearchTool {
+            mmap_threshold_bytes: 1,
+        };
+        let from_reader = reader.execute(input.clone()).await.unwrap();
+        let from_map = mapped.execute(input).await.unwrap();
+
+        let sorted = |matches: Vec<FileMatch>| {
+            let mut values: Vec<_> = matches
+                .into_iter()
+                .map(|m| serde_json::to_value(m).unwrap())
+                .collect();
+            values.sort_by_key(|v| {
+                (
+                    v["file_path"].as_str().unwrap().to_string(),
+                    v["line_number"].as_u64(),
+                )
+            });
+            values
+        };
+        // 600 from the loop, the final line and the line before invalid UTF-8
+        assert_eq!(from_reader.matches.len(), 602);
+        assert_eq!(sorted(from_reader.matches), sorted(from_map.matches));
     }
 }
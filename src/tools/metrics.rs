@@ -0,0 +1,172 @@
+// Server metrics tool implementation with schemars
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::McpTool;
+use crate::server::error::ToolError;
+use crate::shared::state::{AppState, HistogramSummary, MetricValue};
+
+const ALL_METRICS: &str = "all";
+
+fn default_metric_name() -> String {
+    ALL_METRICS.to_string()
+}
+
+/// Input parameters for the metrics tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetMetricsInput {
+    /// Metric to read, or `all`
+    #[schemars(description = "Name of the metric to read, or \"all\" for every metric")]
+    #[serde(default = "default_metric_name")]
+    pub name: String,
+}
+
+/// Output from the metrics tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetMetricsOutput {
+    /// Matching metrics, sorted by name
+    #[schemars(description = "Matching metrics sorted by name")]
+    pub metrics: Vec<MetricEntry>,
+}
+
+/// Current value of a single metric
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MetricEntry {
+    /// Metric name
+    #[schemars(description = "Metric name, e.g. tool_calls_file_search")]
+    pub name: String,
+
+    /// Metric type
+    #[schemars(description = "Metric type: counter, gauge or histogram")]
+    pub kind: String,
+
+    /// Current value
+    #[schemars(description = "Counter or gauge value; the latest sample for histograms")]
+    pub value: f64,
+
+    /// Distribution of histogram samples
+    #[schemars(description = "Count, extremes, mean and percentiles; only set for histograms")]
+    pub summary: Option<HistogramSummary>,
+}
+
+/// Metrics tool implementation
+pub struct GetMetricsTool {
+    state: AppState,
+}
+
+impl GetMetricsTool {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+fn metric_entry(name: &str, value: &MetricValue) -> MetricEntry {
+    let kind = match value {
+        MetricValue::Counter(_) => "counter",
+        MetricValue::Gauge(_) => "gauge",
+        MetricValue::Histogram(_) => "histogram",
+    };
+
+    MetricEntry {
+        name: name.to_string(),
+        kind: kind.to_string(),
+        value: value.as_number(),
+        summary: matches!(value, MetricValue::Histogram(_)).then(|| value.summary()),
+    }
+}
+
+#[async_trait]
+impl McpTool for GetMetricsTool {
+    type Input = GetMetricsInput;
+    type Output = GetMetricsOutput;
+
+    fn name(&self) -> &'static str {
+        "get_metrics"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read the server's own metrics: tool call counters, resource gauges and latency histograms with percentiles."
+    }
+
+    async fn execute(&self, input: Self::Input) -> Result<Self::Output, ToolError> {
+        let mut metrics: Vec<MetricEntry> = if input.name == ALL_METRICS {
+            self.state
+                .metrics
+                .iter()
+                .map(|entry| metric_entry(entry.key(), entry.value()))
+                .collect()
+        } else {
+            let value = self.state.metrics.get(&input.name).ok_or_else(|| {
+                ToolError::InvalidInput(format!("Unknown metric: {}", input.name))
+            })?;
+            vec![metric_entry(&input.name, &value)]
+        };
+        metrics.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(GetMetricsOutput { metrics })
+    }
+
+    fn examples(&self) -> Vec<serde_json::Value> {
+        vec![
+            serde_json::json!({ "name": "all" }),
+            serde_json::json!({ "name": "tool_calls_file_search" }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reads_counter_and_histogram() {
+        let state = AppState::new();
+        state.metrics.insert(
+            "tool_calls_file_search".to_string(),
+            MetricValue::Counter(3),
+        );
+        state.record_histogram("tool_duration_seconds_file_search", 1.0);
+        state.record_histogram("tool_duration_seconds_file_search", 3.0);
+        let tool = GetMetricsTool::new(state);
+
+        let output = tool
+            .execute(GetMetricsInput {
+                name: "tool_calls_file_search".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(output.metrics.len(), 1);
+        assert_eq!(output.metrics[0].kind, "counter");
+        assert_eq!(output.metrics[0].value, 3.0);
+        assert!(output.metrics[0].summary.is_none());
+
+        let output = tool
+            .execute(GetMetricsInput {
+                name: default_metric_name(),
+            })
+            .await
+            .unwrap();
+        let histogram = output
+            .metrics
+            .iter()
+            .find(|m| m.name == "tool_duration_seconds_file_search")
+            .unwrap();
+        assert_eq!(histogram.kind, "histogram");
+        assert_eq!(histogram.value, 3.0);
+        let summary = histogram.summary.unwrap();
+        assert_eq!((summary.count, summary.min, summary.max), (2, 1.0, 3.0));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_metric() {
+        let tool = GetMetricsTool::new(AppState::new());
+        let result = tool
+            .execute(GetMetricsInput {
+                name: "missing".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(ToolError::InvalidInput(_))));
+    }
+}
@@ -2,6 +2,8 @@
 // This module contains the trait definition and tool registry
 
 use crate::server::error::ToolError;
+use crate::shared::config::Config;
+use crate::shared::state::{AppState, ToolCall, ToolCallResult};
 use async_trait::async_trait;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,7 +11,11 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+pub mod circuit_breaker;
+pub mod detect_encoding;
 pub mod file_search;
+pub mod metrics;
+pub mod pool;
 pub mod schemas;
 
 /// Trait for MCP tools that can generate their own schemas
@@ -34,6 +40,11 @@ pub trait McpTool: Send + Sync {
         let schema = schemars::schema_for!(Self::Output);
         serde_json::to_value(&schema).unwrap()
     }
+
+    /// Sample inputs showing how the tool is meant to be called
+    fn examples(&self) -> Vec<serde_json::Value> {
+        Vec::new()
+    }
 }
 
 /// Trait for dynamic tool calling (type-erased)
@@ -43,6 +54,7 @@ pub trait DynamicTool: Send + Sync {
     fn description(&self) -> &'static str;
     fn input_schema(&self) -> serde_json::Value;
     fn output_schema(&self) -> serde_json::Value;
+    fn examples(&self) -> Vec<serde_json::Value>;
     async fn call(&self, input: Value) -> Result<Value, ToolError>;
 }
 
@@ -75,6 +87,10 @@ impl<T: McpTool> DynamicTool for ToolWrapper<T> {
         self.tool.output_schema()
     }
 
+    fn examples(&self) -> Vec<serde_json::Value> {
+        self.tool.examples()
+    }
+
     async fn call(&self, input: Value) -> Result<Value, ToolError> {
         let typed_input: T::Input =
             serde_json::from_value(input).map_err(|e| ToolError::InvalidInput(e.to_string()))?;
@@ -89,15 +105,42 @@ impl<T: McpTool> DynamicTool for ToolWrapper<T> {
 #[derive(Clone)]
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn DynamicTool>>,
+    validate_output: bool,
+    state: Option<AppState>,
 }
 
 impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            validate_output: false,
+            state: None,
         }
     }
 
+    /// Registry holding every tool this server provides
+    pub fn with_default_tools(config: &Config) -> Self {
+        let mut registry = Self::new();
+        registry.register(file_search::FileSearchTool::new(&config.resource_limits));
+        registry.register(detect_encoding::DetectEncodingTool::new(&config.security));
+        registry.with_output_validation(config.development.validate_tool_output)
+    }
+
+    /// Check each successful result against the tool's declared output schema.
+    ///
+    /// Meant for development and testing: a mismatch is logged and returned as
+    /// a `SerializationError` instead of being passed on to the client.
+    pub fn with_output_validation(mut self, enabled: bool) -> Self {
+        self.validate_output = enabled;
+        self
+    }
+
+    /// Record every call in `state`'s tool call history and metrics
+    pub fn with_state(mut self, state: AppState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
     /// Register a tool
     pub fn register<T: McpTool + 'static>(&mut self, tool: T) {
         let name = tool.name().to_string();
@@ -109,23 +152,67 @@ impl ToolRegistry {
     pub fn list_tools(&self) -> Vec<ToolInfo> {
         self.tools
             .values()
-            .map(|tool| ToolInfo {
-                name: tool.name().to_string(),
-                description: tool.description().to_string(),
-                input_schema: tool.input_schema(),
-                output_schema: tool.output_schema(),
-            })
+            .map(|tool| tool_info(tool.as_ref()))
             .collect()
     }
 
-    /// Call a tool by name
+    /// Get a single registered tool by name
+    pub fn get_tool(&self, name: &str) -> Option<ToolInfo> {
+        self.tools.get(name).map(|tool| tool_info(tool.as_ref()))
+    }
+
+    /// Call a tool by name, recording it if the registry has state
+    // Allow dead_code: Public API for callers that use the registry directly
+    #[allow(dead_code)]
     pub async fn call_tool(&self, name: &str, input: Value) -> Result<Value, ToolError> {
+        self.execute(ToolCall::new(name.to_string(), input)).await
+    }
+
+    /// Run `call`, recording it if the registry has state.
+    ///
+    /// Callers that need the call's id or caller metadata build the
+    /// [`ToolCall`] themselves.
+    pub async fn execute(&self, call: ToolCall) -> Result<Value, ToolError> {
+        let Some(state) = &self.state else {
+            return self.dispatch(&call.name, call.arguments).await;
+        };
+
+        let _ = state.record_tool_call(call.clone()).await;
+
+        let start_time = std::time::Instant::now();
+        let result = self.dispatch(&call.name, call.arguments.clone()).await;
+        let outcome = match &result {
+            Ok(output) => ToolCallResult::Success(output.clone()),
+            Err(e) => ToolCallResult::Error(e.to_string()),
+        };
+        let _ = state
+            .complete_tool_call(call.complete(outcome, start_time.elapsed().as_millis() as u64))
+            .await;
+
+        result
+    }
+
+    /// Record `call` as failed with `error` without running it
+    pub async fn reject(&self, call: ToolCall, error: ToolError) -> Result<Value, ToolError> {
+        if let Some(state) = &self.state {
+            let _ = state.record_tool_call(call.clone()).await;
+            let failed = call.complete(ToolCallResult::Error(error.to_string()), 0);
+            let _ = state.complete_tool_call(failed).await;
+        }
+        Err(error)
+    }
+
+    async fn dispatch(&self, name: &str, input: Value) -> Result<Value, ToolError> {
         let tool = self
             .tools
             .get(name)
             .ok_or_else(|| ToolError::ToolNotFound(name.to_string()))?;
 
-        tool.call(input).await
+        let output = tool.call(input).await?;
+        if self.validate_output {
+            validate_output(name, &tool.output_schema(), &output)?;
+        }
+        Ok(output)
     }
 
     /// Get tool count
@@ -134,6 +221,39 @@ impl ToolRegistry {
     }
 }
 
+fn tool_info(tool: &dyn DynamicTool) -> ToolInfo {
+    ToolInfo {
+        name: tool.name().to_string(),
+        description: tool.description().to_string(),
+        input_schema: tool.input_schema(),
+        output_schema: tool.output_schema(),
+        examples: tool.examples(),
+    }
+}
+
+/// Validate a tool result against its output schema
+fn validate_output(tool: &str, schema: &Value, output: &Value) -> Result<(), ToolError> {
+    let validator = jsonschema::JSONSchema::compile(schema).map_err(|e| {
+        ToolError::SerializationError(format!("Invalid output schema for {tool}: {e}"))
+    })?;
+
+    if let Err(errors) = validator.validate(output) {
+        let details: Vec<String> = errors
+            .map(|e| format!("{}: {e}", e.instance_path))
+            .collect();
+        tracing::warn!(
+            "Output of {tool} does not match its output schema: {}",
+            details.join("; ")
+        );
+        return Err(ToolError::SerializationError(format!(
+            "Output of {tool} does not match its output schema: {}",
+            details.join("; ")
+        )));
+    }
+
+    Ok(())
+}
+
 impl Default for ToolRegistry {
     fn default() -> Self {
         Self::new()
@@ -147,4 +267,5 @@ pub struct ToolInfo {
     pub description: String,
     pub input_schema: serde_json::Value,
     pub output_schema: serde_json::Value,
+    pub examples: Vec<serde_json::Value>,
 }
@@ -0,0 +1,174 @@
+// Warm worker pool for tool execution
+// A fixed number of long-lived worker tasks pull tool calls from a shared queue,
+// avoiding a task spawn per call and bounding how many tools run at once.
+
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+use super::ToolRegistry;
+use crate::server::error::ToolError;
+use crate::shared::state::ToolCall;
+
+/// A queued tool call waiting for a free worker
+struct ToolJob {
+    call: ToolCall,
+    reply: oneshot::Sender<Result<Value, ToolError>>,
+}
+
+/// Pool of pre-spawned worker tasks executing calls against a `ToolRegistry`
+#[derive(Clone)]
+pub struct ToolWorkerPool {
+    job_tx: mpsc::Sender<ToolJob>,
+    size: usize,
+}
+
+impl ToolWorkerPool {
+    /// Spawn `size` workers (at least one) serving calls for `registry`.
+    ///
+    /// Must be called from within a Tokio runtime. Workers exit once every
+    /// clone of the pool has been dropped.
+    pub fn new(registry: ToolRegistry, size: usize) -> Self {
+        let size = size.max(1);
+        let (job_tx, job_rx) = mpsc::channel::<ToolJob>(size * 4);
+        let job_rx = Arc::new(Mutex::new(job_rx));
+
+        for _ in 0..size {
+            let registry = registry.clone();
+            let job_rx = job_rx.clone();
+            tokio::spawn(async move {
+                loop {
+                    // Only one idle worker waits on the queue at a time
+                    let job = job_rx.lock().await.recv().await;
+                    let Some(job) = job else {
+                        break;
+                    };
+
+                    let result = registry.execute(job.call).await;
+                    let _ = job.reply.send(result);
+                }
+            });
+        }
+
+        Self { job_tx, size }
+    }
+
+    /// Queue a tool call and wait for a worker to execute it
+    // Allow dead_code: Public API for callers without caller metadata
+    #[allow(dead_code)]
+    pub async fn call_tool(&self, name: &str, input: Value) -> Result<Value, ToolError> {
+        self.execute(ToolCall::new(name.to_string(), input)).await
+    }
+
+    /// Queue `call` and wait for a worker to run it through
+    /// [`ToolRegistry::execute`]
+    pub async fn execute(&self, call: ToolCall) -> Result<Value, ToolError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let job = ToolJob {
+            call,
+            reply: reply_tx,
+        };
+
+        self.job_tx
+            .send(job)
+            .await
+            .map_err(|_| ToolError::ExecutionError("Tool worker pool is shut down".to_string()))?;
+
+        reply_rx.await.map_err(|_| {
+            ToolError::ExecutionError("Tool worker exited before completing the call".to_string())
+        })?
+    }
+
+    /// Number of worker tasks in the pool
+    #[allow(dead_code)]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::McpTool;
+    use async_trait::async_trait;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct EchoInput {
+        value: u32,
+    }
+
+    #[derive(Debug, Serialize, JsonSchema)]
+    struct EchoOutput {
+        value: u32,
+    }
+
+    /// Slow echo tool that tracks how many calls run at once
+    struct SlowEchoTool {
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl McpTool for SlowEchoTool {
+        type Input = EchoInput;
+        type Output = EchoOutput;
+
+        fn name(&self) -> &'static str {
+            "slow_echo"
+        }
+
+        fn description(&self) -> &'static str {
+            "Echoes its input after a short delay"
+        }
+
+        async fn execute(&self, input: Self::Input) -> Result<Self::Output, ToolError> {
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(EchoOutput { value: input.value })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_executes_calls_and_bounds_concurrency() {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut registry = ToolRegistry::new();
+        registry.register(SlowEchoTool {
+            running: Arc::new(AtomicUsize::new(0)),
+            peak: peak.clone(),
+        });
+
+        let pool = ToolWorkerPool::new(registry, 2);
+        assert_eq!(pool.size(), 2);
+
+        let mut handles = Vec::new();
+        for i in 0..8 {
+            let pool = pool.clone();
+            handles.push(tokio::spawn(async move {
+                pool.call_tool("slow_echo", serde_json::json!({ "value": i }))
+                    .await
+            }));
+        }
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            let result = handle.await.unwrap().unwrap();
+            assert_eq!(result["value"], i as u64);
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_pool_reports_unknown_tool() {
+        let pool = ToolWorkerPool::new(ToolRegistry::new(), 1);
+
+        let result = pool.call_tool("missing", serde_json::json!({})).await;
+        assert!(matches!(result, Err(ToolError::ToolNotFound(_))));
+    }
+}
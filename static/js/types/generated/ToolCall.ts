@@ -11,12 +11,15 @@ export interface ToolCall {
   result_string: string | null;
   success: boolean;
   error: string | null;
+  caller: string | null;
+  tags: Array<string>;
+  request_id: string | null;
 }
 
 export type ToolCallResult = { Success: any } | { Error: string };
 
 export interface SystemEvent {
-  type: 'McpConnected' | 'McpDisconnected' | 'ToolCalled' | 'ResourceAccessed' | 'Error' | 'Custom';
+  type: 'McpConnected' | 'McpDisconnected' | 'ToolCalled' | 'ResourceAccessed' | 'Error' | 'SessionStarted' | 'SessionExpired' | 'Custom';
   data?: {
     name?: string;
     id?: string;
@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use rust_mcp_server::shared::config::{Config, TimestampFormat};
+use tempfile::TempDir;
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/config.toml")
+}
+
+/// Test that a TOML file fills nested sections and leaves the rest at defaults
+#[test]
+fn test_config_file_nested_fields() {
+    let config = Config::from_file(&fixture()).unwrap();
+    let defaults = Config::default();
+
+    assert_eq!(config.server.dashboard_port, 9090);
+    assert_eq!(config.server.timestamp_format, TimestampFormat::EpochMillis);
+    assert!(config.server.enable_tool_examples);
+    assert_eq!(config.security.max_file_size_bytes, 2 * 1024 * 1024);
+    assert_eq!(config.security.allowed_file_extensions, vec!["rs", "toml"]);
+    assert_eq!(config.resource_limits.max_tool_call_history, 250);
+
+    assert_eq!(config.prompts.len(), 1);
+    assert_eq!(config.prompts[0].name, "review");
+    assert_eq!(config.prompts[0].arguments.len(), 2);
+    assert!(config.prompts[0].arguments[0].required);
+    assert!(!config.prompts[0].arguments[1].required);
+
+    // Omitted settings keep their defaults
+    assert_eq!(config.server.dashboard_host, defaults.server.dashboard_host);
+    assert_eq!(
+        config.security.max_tool_execution_time_ms,
+        defaults.security.max_tool_execution_time_ms
+    );
+    assert_eq!(
+        config.rate_limiting.requests_per_minute,
+        defaults.rate_limiting.requests_per_minute
+    );
+    assert!(config.validate().is_ok());
+}
+
+/// Test that environment variables take precedence over file values
+#[test]
+fn test_env_overrides_config_file() {
+    std::env::set_var("HTTP_TIMEOUT_SECONDS", "45");
+    let config = Config::from_file(&fixture())
+        .and_then(Config::with_env)
+        .unwrap();
+    std::env::remove_var("HTTP_TIMEOUT_SECONDS");
+
+    assert_eq!(config.resource_limits.http_timeout_seconds, 45);
+    assert_eq!(config.resource_limits.max_tool_call_history, 250);
+}
+
+/// Test that malformed files are reported as configuration errors
+#[test]
+fn test_invalid_config_file_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+
+    std::fs::write(&path, "[security]\nmax_file_size_bytes = \"lots\"\n").unwrap();
+    assert!(Config::from_file(&path).is_err());
+
+    assert!(Config::from_file(&temp_dir.path().join("missing.toml")).is_err());
+}
@@ -0,0 +1,63 @@
+use actix_web::{web, App};
+use rust_mcp_server::dashboard::handlers::{debug_config, get_config};
+use rust_mcp_server::shared::config::Config;
+
+/// Config whose OTLP endpoint and log filter embed secrets from the environment
+fn config_with_secrets() -> Config {
+    std::env::set_var("REDACTION_TEST_TOKEN", "tok-4f9a2c");
+    std::env::set_var("REDACTION_TEST_SECRET", "sec-81be07");
+
+    let mut config = Config::default();
+    config.server.otlp_endpoint = Some("https://collector.local/?token=tok-4f9a2c".to_string());
+    config.server.log_level = "info,auth[key=sec-81be07]=debug".to_string();
+    config
+}
+
+/// Test that values of `*_TOKEN` and `*_SECRET` variables are masked
+#[test]
+fn test_redacted_masks_secret_env_values() {
+    let config = config_with_secrets();
+    let redacted = config.redacted();
+
+    assert_eq!(
+        redacted["server"]["otlp_endpoint"],
+        "https://collector.local/?token=[REDACTED]"
+    );
+    assert_eq!(
+        redacted["server"]["log_level"],
+        "info,auth[key=[REDACTED]]=debug"
+    );
+    assert_eq!(
+        redacted["server"]["dashboard_port"],
+        config.server.dashboard_port
+    );
+}
+
+/// Test that `/api/config` is redacted while `/debug/config` keeps the full dump
+#[actix_web::test]
+async fn test_api_config_redacted_debug_config_full() {
+    let app = actix_web::test::init_service(
+        App::new()
+            .app_data(web::Data::new(config_with_secrets()))
+            .route("/api/config", web::get().to(get_config))
+            .route("/debug/config", web::get().to(debug_config)),
+    )
+    .await;
+
+    let req = actix_web::test::TestRequest::get()
+        .uri("/api/config")
+        .to_request();
+    let body = actix_web::test::read_body(actix_web::test::call_service(&app, req).await).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(!body.contains("tok-4f9a2c"));
+    assert!(body.contains("[REDACTED]"));
+
+    let req = actix_web::test::TestRequest::get()
+        .uri("/debug/config")
+        .to_request();
+    let full: serde_json::Value = actix_web::test::call_and_read_body_json(&app, req).await;
+    assert_eq!(
+        full["config"]["server"]["otlp_endpoint"],
+        "https://collector.local/?token=tok-4f9a2c"
+    );
+}
@@ -0,0 +1,73 @@
+use actix_web::{web, App};
+use rust_mcp_server::dashboard::handlers::stream_directory;
+use rust_mcp_server::shared::config::Config;
+use rust_mcp_server::shared::reload::{self, apply_reload};
+use tempfile::TempDir;
+
+/// Test that a reloaded setting takes effect on the next request
+#[actix_web::test]
+async fn test_reload_applies_to_subsequent_requests() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("file.txt"), "data").unwrap();
+
+    let live_config = reload::shared(Config::default());
+    let app = actix_web::test::init_service(
+        App::new()
+            .app_data(web::Data::new(live_config.clone()))
+            .route("/api/directory/stream", web::get().to(stream_directory)),
+    )
+    .await;
+    let uri = format!("/api/directory/stream?path={}", temp_dir.path().display());
+
+    let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+    let resp = actix_web::test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+    let mut new_config = Config::default();
+    new_config.security.enable_directory_streaming = true;
+    new_config.security.directory_streaming_roots = vec![temp_dir.path().display().to_string()];
+    let restart_required = apply_reload(&live_config, new_config);
+    assert!(restart_required.is_empty());
+
+    let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+    let resp = actix_web::test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+}
+
+/// Test that startup-only settings are reported and left unchanged
+#[test]
+fn test_reload_keeps_startup_only_settings() {
+    let live_config = reload::shared(Config::default());
+
+    let mut new_config = Config::default();
+    new_config.server.dashboard_port = 9090;
+    new_config.resource_limits.tool_worker_pool_size = 4;
+    new_config.resource_limits.http_timeout_seconds = 5;
+    new_config.security.websocket_allowed_origins = vec!["https://example.com".to_string()];
+
+    new_config.security.max_file_size_bytes = 1024;
+    new_config.rate_limiting.requests_per_minute = 5;
+
+    let mut restart_required = apply_reload(&live_config, new_config);
+    restart_required.sort();
+    assert_eq!(
+        restart_required,
+        vec![
+            "resource_limits.http_timeout_seconds",
+            "resource_limits.tool_worker_pool_size",
+            "security.max_file_size_bytes",
+            "server.dashboard_port",
+        ]
+    );
+
+    let current = live_config.load();
+    assert_eq!(current.server.dashboard_port, 8080);
+    assert_eq!(current.resource_limits.tool_worker_pool_size, 0);
+    assert_eq!(current.resource_limits.http_timeout_seconds, 30);
+    assert_eq!(current.security.max_file_size_bytes, 10 * 1024 * 1024);
+    assert_eq!(current.rate_limiting.requests_per_minute, 5);
+    assert_eq!(
+        current.security.websocket_allowed_origins,
+        vec!["https://example.com".to_string()]
+    );
+}
@@ -0,0 +1,51 @@
+use actix_web::http::{header, Method};
+use actix_web::{test, web, App, HttpResponse};
+use rust_mcp_server::dashboard::server::cors;
+use rust_mcp_server::shared::config::Config;
+
+/// Send a preflight for `/api/status` from `origin` and return the allowed origin, if any
+async fn preflight(config: Config, origin: &str) -> Option<String> {
+    let app = test::init_service(
+        App::new()
+            .wrap(cors(&config))
+            .route("/api/status", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+
+    let req = test::TestRequest::default()
+        .method(Method::OPTIONS)
+        .uri("/api/status")
+        .insert_header((header::ORIGIN, origin))
+        .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "GET"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    resp.headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+/// Test that any origin is allowed when CORS is enabled
+#[actix_web::test]
+async fn test_cors_enabled_allows_any_origin() {
+    let mut config = Config::default();
+    config.development.enable_cors = true;
+
+    assert_eq!(
+        preflight(config, "http://example.com").await.as_deref(),
+        Some("http://example.com")
+    );
+}
+
+/// Test that only configured origins get CORS headers when CORS is disabled
+#[actix_web::test]
+async fn test_cors_disabled_restricts_to_allowed_origins() {
+    let mut config = Config::default();
+    config.development.enable_cors = false;
+    config.security.websocket_allowed_origins = vec!["http://localhost:8080".to_string()];
+
+    assert_eq!(preflight(config.clone(), "http://example.com").await, None);
+    assert_eq!(
+        preflight(config, "http://localhost:8080").await.as_deref(),
+        Some("http://localhost:8080")
+    );
+}
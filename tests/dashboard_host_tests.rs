@@ -0,0 +1,118 @@
+use rust_mcp_server::shared::config::Config;
+
+fn config_with_host(host: &str, resolve: bool) -> Config {
+    let mut config = Config::default();
+    config.development.enable_cors = true;
+    config.server.dashboard_host = host.to_string();
+    config.server.resolve_dashboard_host = resolve;
+    config
+}
+
+/// Test that IP addresses and RFC 1123 hostnames are accepted
+#[test]
+fn test_valid_hosts() {
+    for host in [
+        "localhost",
+        "127.0.0.1",
+        "0.0.0.0",
+        "::1",
+        "10.1.2.3",
+        "dashboard",
+        "mcp-server.internal",
+        "a1.b2-c3.example.com",
+        "unix:/run/mcp.sock",
+    ] {
+        assert!(
+            config_with_host(host, false).validate().is_ok(),
+            "{host} should be valid"
+        );
+    }
+}
+
+/// Test that malformed hostnames are rejected upfront
+#[test]
+fn test_invalid_hosts() {
+    let too_long_label = format!("{}.example.com", "a".repeat(64));
+    for host in [
+        "",
+        "unix:",
+        "under_score.example.com",
+        "-leading.example.com",
+        "trailing-.example.com",
+        "double..dot.example.com",
+        ".example.com",
+        "spa ce.example.com",
+        too_long_label.as_str(),
+    ] {
+        let err = config_with_host(host, false).validate().unwrap_err();
+        assert!(
+            err.to_string().contains("not a valid host format"),
+            "{host:?} should be rejected, got {err}"
+        );
+    }
+}
+
+/// Test that an unresolvable name fails validation only when resolution is enabled
+#[test]
+fn test_unresolvable_host_with_resolution() {
+    // `.invalid` is reserved and never resolves (RFC 6761)
+    let host = "mcp-dashboard.invalid";
+    assert!(config_with_host(host, false).validate().is_ok());
+
+    let err = config_with_host(host, true).validate().unwrap_err();
+    assert!(
+        err.to_string().contains("could not be resolved")
+            || err.to_string().contains("did not resolve")
+    );
+
+    assert!(config_with_host("127.0.0.1", true).validate().is_ok());
+}
+
+/// Test that socket paths keep their case and skip DNS resolution
+#[test]
+fn test_unix_socket_host() {
+    let config = config_with_host("unix:/run/MCP.sock", true);
+    assert!(config.validate().is_ok());
+    assert_eq!(
+        config.dashboard_socket_path(),
+        Some(std::path::Path::new("/run/MCP.sock"))
+    );
+    assert_eq!(
+        config_with_host("localhost", false).dashboard_socket_path(),
+        None
+    );
+}
+
+/// Test that the dashboard serves over a Unix socket, replacing a stale one
+#[cfg(unix)]
+#[actix_web::test]
+async fn test_dashboard_over_unix_socket() {
+    use rust_mcp_server::dashboard::server::run_dashboard_with_config;
+    use rust_mcp_server::shared::state::AppState;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dashboard.sock");
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+
+    let config = config_with_host(&format!("unix:{}", path.display()), false);
+    actix_web::rt::spawn(run_dashboard_with_config(AppState::new(), config, false));
+
+    let mut stream = None;
+    for _ in 0..50 {
+        if let Ok(s) = tokio::net::UnixStream::connect(&path).await {
+            stream = Some(s);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let mut stream = stream.expect("dashboard did not listen on the socket");
+
+    stream
+        .write_all(b"GET /health/live HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+}
@@ -0,0 +1,39 @@
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::server::debug_routes;
+use rust_mcp_server::shared::{config::Config, state::AppState};
+
+async fn fetch_status(enable_debug_routes: bool, uri: &str) -> StatusCode {
+    let mut config = Config::default();
+    config.development.enable_debug_routes = enable_debug_routes;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new()))
+            .app_data(web::Data::new(config))
+            .configure(|cfg| debug_routes(cfg, enable_debug_routes)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri(uri).to_request();
+    test::call_service(&app, req).await.status()
+}
+
+/// Test that the debug scope is not mounted when debug routes are disabled
+#[actix_web::test]
+async fn test_debug_routes_hidden_when_disabled() {
+    assert_eq!(
+        fetch_status(false, "/debug/state").await,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        fetch_status(false, "/debug/config").await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+/// Test that the debug scope is served when debug routes are enabled
+#[actix_web::test]
+async fn test_debug_routes_served_when_enabled() {
+    assert_eq!(fetch_status(true, "/debug/state").await, StatusCode::OK);
+}
@@ -0,0 +1,86 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::get_diagnostics;
+use rust_mcp_server::shared::{
+    config::Config,
+    state::{AppState, ToolCall, ToolCallResult},
+};
+use rust_mcp_server::tools::ToolRegistry;
+
+async fn fetch_diagnostics(state: AppState, config: Config) -> actix_web::dev::ServiceResponse {
+    let registry = ToolRegistry::with_default_tools(&config);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(registry))
+            .route("/api/diagnostics", web::get().to(get_diagnostics)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/diagnostics")
+        .to_request();
+    test::call_service(&app, req).await
+}
+
+/// Test that the bundle carries status, version, metrics and recent failures
+#[actix_web::test]
+async fn test_diagnostics_bundle_contents() {
+    let state = AppState::new();
+    let call = ToolCall::new("file_search".to_string(), serde_json::json!({}));
+    state
+        .record_tool_call(call.complete(ToolCallResult::Error("boom".to_string()), 5))
+        .await
+        .unwrap();
+
+    let mut config = Config::default();
+    config.server.enable_diagnostics = true;
+    let resp = fetch_diagnostics(state, config).await;
+    assert!(resp.status().is_success());
+
+    let disposition = resp
+        .headers()
+        .get("Content-Disposition")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(disposition.starts_with("attachment; filename=\"mcp-diagnostics-"));
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["version"]["version"], env!("CARGO_PKG_VERSION"));
+    assert!(body["status"]["mcp_status"]["connected"].is_boolean());
+    assert_eq!(body["metrics"]["total_tool_calls"], 1);
+    assert_eq!(body["metrics"]["success_rate"], 0.0);
+    assert_eq!(
+        body["recent_errors"]["failed_tool_calls"][0]["error"],
+        "boom"
+    );
+    assert!(body["config"]["server"].is_object());
+}
+
+/// Test that the endpoint is hidden unless enabled
+#[actix_web::test]
+async fn test_diagnostics_disabled_by_default() {
+    let resp = fetch_diagnostics(AppState::new(), Config::default()).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+}
+
+/// Test that the bundled config masks secrets taken from the environment
+#[actix_web::test]
+async fn test_diagnostics_config_redacted() {
+    std::env::set_var("DIAGNOSTICS_TEST_TOKEN", "diag-7c31e5");
+
+    let mut config = Config::default();
+    config.server.enable_diagnostics = true;
+    config.server.otlp_endpoint = Some("https://collector.local/?token=diag-7c31e5".to_string());
+    let resp = fetch_diagnostics(AppState::new(), config).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(
+        body["config"]["server"]["otlp_endpoint"],
+        "https://collector.local/?token=[REDACTED]"
+    );
+    assert!(!body.to_string().contains("diag-7c31e5"));
+}
@@ -0,0 +1,122 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::stream_directory;
+use rust_mcp_server::shared::{config::Config, reload};
+use tempfile::TempDir;
+
+/// Test that directory entries are streamed as NDJSON and all are delivered
+#[actix_web::test]
+async fn test_directory_stream_emits_all_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    for i in 0..250 {
+        std::fs::write(temp_dir.path().join(format!("file_{i}.txt")), "data").unwrap();
+    }
+    std::fs::create_dir(temp_dir.path().join("nested")).unwrap();
+
+    let mut config = Config::default();
+    config.security.enable_directory_streaming = true;
+    config.security.directory_streaming_roots = vec![temp_dir.path().display().to_string()];
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(reload::shared(config)))
+            .route("/api/directory/stream", web::get().to(stream_directory)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/api/directory/stream?path={}",
+            temp_dir.path().display()
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/x-ndjson"
+    );
+
+    let body = test::read_body(resp).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let entries: Vec<serde_json::Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line should be valid JSON"))
+        .collect();
+
+    assert_eq!(entries.len(), 251);
+    assert_eq!(
+        entries.iter().filter(|e| e["is_dir"] == true).count(),
+        1,
+        "nested directory should be reported as a directory"
+    );
+}
+
+/// Test that the endpoint is unavailable unless explicitly enabled
+#[actix_web::test]
+async fn test_directory_stream_disabled_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(reload::shared(Config::default())))
+            .route("/api/directory/stream", web::get().to(stream_directory)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/api/directory/stream?path={}",
+            temp_dir.path().display()
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+}
+
+/// Test that directories outside the configured roots are refused, even via `..`
+#[actix_web::test]
+async fn test_directory_stream_outside_roots_forbidden() {
+    let root = TempDir::new().unwrap();
+    let outside = TempDir::new().unwrap();
+    std::fs::create_dir(root.path().join("inside")).unwrap();
+
+    let mut config = Config::default();
+    config.security.enable_directory_streaming = true;
+    config.security.directory_streaming_roots = vec![root.path().display().to_string()];
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(reload::shared(config)))
+            .route("/api/directory/stream", web::get().to(stream_directory)),
+    )
+    .await;
+
+    let status = |path: String| {
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/directory/stream?path={path}"))
+            .to_request();
+        let app = &app;
+        async move { test::call_service(app, req).await.status() }
+    };
+
+    assert!(status(root.path().join("inside").display().to_string())
+        .await
+        .is_success());
+    assert_eq!(
+        status(outside.path().display().to_string()).await,
+        actix_web::http::StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(root.path().join("inside/../..").display().to_string()).await,
+        actix_web::http::StatusCode::FORBIDDEN
+    );
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/api/directory/stream?path={}",
+            outside.path().display()
+        ))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["error_type"], "validation");
+}
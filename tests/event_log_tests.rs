@@ -0,0 +1,89 @@
+use std::path::Path;
+use std::time::Duration;
+
+use rust_mcp_server::shared::event_log::{rotated_path, EventLog};
+use rust_mcp_server::shared::state::{AppState, SystemEvent};
+use tempfile::TempDir;
+
+/// Wait for the background writer to flush `expected` lines to the log
+async fn wait_for_lines(path: &Path, expected: usize) -> Vec<String> {
+    for _ in 0..100 {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        let lines: Vec<String> = contents.lines().map(str::to_string).collect();
+        if lines.len() >= expected {
+            return lines;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("event log never reached {expected} lines");
+}
+
+/// Test that emitted events are written as timestamped JSONL
+#[tokio::test]
+async fn test_events_written_as_jsonl() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("events.jsonl");
+
+    let state = AppState::new();
+    EventLog::open(&log_path, 1024 * 1024)
+        .await
+        .unwrap()
+        .spawn(&state);
+
+    state.event_tx.send(SystemEvent::McpConnected).unwrap();
+    state
+        .event_tx
+        .send(SystemEvent::Error {
+            message: "disk full".to_string(),
+        })
+        .unwrap();
+
+    let lines = wait_for_lines(&log_path, 2).await;
+    assert_eq!(lines.len(), 2);
+
+    let records: Vec<serde_json::Value> = lines
+        .iter()
+        .map(|line| serde_json::from_str(line).expect("each line should be valid JSON"))
+        .collect();
+    for record in &records {
+        let timestamp = record["timestamp"].as_str().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(timestamp).is_ok());
+    }
+    assert_eq!(records[0]["event"], "McpConnected");
+    assert_eq!(records[1]["event"]["Error"]["message"], "disk full");
+}
+
+/// Test that the log rotates once it exceeds the size limit
+#[tokio::test]
+async fn test_event_log_rotation() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("events.jsonl");
+
+    let state = AppState::new();
+    // Small enough that every event lands in its own file
+    EventLog::open(&log_path, 16).await.unwrap().spawn(&state);
+
+    for i in 0..3 {
+        state
+            .event_tx
+            .send(SystemEvent::Custom(format!("event_{i}")))
+            .unwrap();
+    }
+
+    let oldest = rotated_path(&log_path, 2);
+    for _ in 0..100 {
+        if oldest.exists() && log_path.metadata().map(|m| m.len()).unwrap_or(0) > 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    let read_event = |path: &Path| {
+        let line = std::fs::read_to_string(path).unwrap();
+        let record: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        record["event"]["Custom"].as_str().unwrap().to_string()
+    };
+    assert_eq!(read_event(&oldest), "event_0");
+    assert_eq!(read_event(&rotated_path(&log_path, 1)), "event_1");
+    assert_eq!(read_event(&log_path), "event_2");
+}
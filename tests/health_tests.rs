@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::{health_check, health_live};
+use rust_mcp_server::shared::{
+    config::Config,
+    state::{AppState, McpStatus},
+};
+
+async fn fetch(config: Config, state: AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(state))
+            .route("/health", web::get().to(health_check))
+            .route("/health/live", web::get().to(health_live)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri(uri).to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+fn state_with_status(connected: bool, heartbeat_age_seconds: i64) -> AppState {
+    let state = AppState::new();
+    state.mcp_status.store(Arc::new(McpStatus {
+        connected,
+        last_heartbeat: Some(chrono::Utc::now() - chrono::Duration::seconds(heartbeat_age_seconds)),
+        ..McpStatus::default()
+    }));
+    state
+}
+
+/// Test that a connected server with a fresh heartbeat is healthy
+#[actix_web::test]
+async fn test_health_healthy() {
+    let mut config = Config::default();
+    config.server.health_heartbeat_max_age_seconds = 60;
+
+    let (status, body) = fetch(config, state_with_status(true, 5), "/health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "healthy");
+    assert_eq!(body["checks"]["mcp"]["healthy"], true);
+    assert_eq!(body["checks"]["heartbeat"]["healthy"], true);
+    assert!(body["checks"]["heartbeat"]["age_seconds"].as_u64().unwrap() >= 5);
+    assert!(body["checks"]["static_dir"]["healthy"].is_boolean());
+}
+
+/// Test that a disconnected server or stale heartbeat returns 503
+#[actix_web::test]
+async fn test_health_degraded() {
+    let (status, body) = fetch(Config::default(), state_with_status(false, 0), "/health").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["checks"]["mcp"]["healthy"], false);
+
+    let mut config = Config::default();
+    config.server.health_heartbeat_max_age_seconds = 60;
+    let (status, body) = fetch(config, state_with_status(true, 120), "/health").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["checks"]["heartbeat"]["healthy"], false);
+
+    // Heartbeat age is not checked unless a threshold is set
+    let (status, _) = fetch(Config::default(), state_with_status(true, 120), "/health").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Test that liveness succeeds even while degraded
+#[actix_web::test]
+async fn test_health_live_always_ok() {
+    let (status, body) = fetch(
+        Config::default(),
+        state_with_status(false, 0),
+        "/health/live",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "alive");
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::{get_heartbeat, get_status};
+use rust_mcp_server::shared::{
+    config::Config,
+    state::{AppState, McpStatus},
+};
+
+/// Test that an old heartbeat is reported as stale instead of being refreshed
+#[actix_web::test]
+async fn test_status_reports_stale_heartbeat() {
+    let state = AppState::new();
+    let old_heartbeat = chrono::Utc::now() - chrono::Duration::minutes(10);
+    state.mcp_status.store(Arc::new(McpStatus {
+        last_heartbeat: Some(old_heartbeat),
+        ..McpStatus::default()
+    }));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .app_data(web::Data::new(Config::default()))
+            .route("/api/status", web::get().to(get_status))
+            .route("/api/heartbeat", web::get().to(get_heartbeat)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/status").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("heartbeat-stale"));
+    assert!(body.contains(&old_heartbeat.format("%Y-%m-%d %H:%M:%S UTC").to_string()));
+    assert_eq!(state.mcp_status.load().last_heartbeat, Some(old_heartbeat));
+
+    let req = test::TestRequest::get().uri("/api/heartbeat").to_request();
+    let heartbeat: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(heartbeat["is_stale"], true);
+    assert_eq!(state.mcp_status.load().last_heartbeat, Some(old_heartbeat));
+}
+
+/// Test that a fresh heartbeat is not flagged
+#[actix_web::test]
+async fn test_fresh_heartbeat_not_stale() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new()))
+            .app_data(web::Data::new(Config::default()))
+            .route("/api/status", web::get().to(get_status))
+            .route("/api/heartbeat", web::get().to(get_heartbeat)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/status").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    assert!(!String::from_utf8(body.to_vec())
+        .unwrap()
+        .contains("heartbeat-stale"));
+
+    let req = test::TestRequest::get().uri("/api/heartbeat").to_request();
+    let heartbeat: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(heartbeat["is_stale"], false);
+}
@@ -39,15 +39,11 @@ async fn test_hot_reload_watcher_creation() {
 /// Test that the watcher can be gracefully shut down
 #[tokio::test]
 async fn test_hot_reload_graceful_shutdown() {
-    let _temp_dir = create_temp_workspace();
-    let original_dir = std::env::current_dir().expect("Failed to get current dir");
-
-    // Change to temp directory for the test
-    std::env::set_current_dir(_temp_dir.path()).expect("Failed to change directory");
+    let temp_dir = create_temp_workspace();
 
     let state = AppState::new();
     let config = Config::default();
-    let (watcher, _reload_rx) = HotReloadWatcher::new(state, config);
+    let (watcher, _reload_rx) = HotReloadWatcher::with_root(state, config, temp_dir.path());
 
     // Create shutdown channel
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
@@ -66,9 +62,6 @@ async fn test_hot_reload_graceful_shutdown() {
     // Wait for watcher to shut down
     let result = tokio::time::timeout(Duration::from_secs(5), watcher_handle).await;
 
-    // Restore original directory
-    std::env::set_current_dir(original_dir).expect("Failed to restore directory");
-
     assert!(result.is_ok(), "Watcher did not shut down gracefully");
     assert!(
         result.unwrap().is_ok(),
@@ -76,6 +69,45 @@ async fn test_hot_reload_graceful_shutdown() {
     );
 }
 
+/// Test that a second watcher over the same directories is rejected until the first stops
+#[tokio::test]
+async fn test_second_watcher_on_same_paths_rejected() {
+    let temp_dir = create_temp_workspace();
+
+    let (first, _first_rx) =
+        HotReloadWatcher::with_root(AppState::new(), Config::default(), temp_dir.path());
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let first_handle = tokio::spawn(async move { first.start(shutdown_rx).await });
+
+    // Give the first watcher time to claim its directories
+    sleep(Duration::from_millis(100)).await;
+
+    let (second, _second_rx) =
+        HotReloadWatcher::with_root(AppState::new(), Config::default(), temp_dir.path());
+    let (_second_shutdown_tx, second_shutdown_rx) = oneshot::channel();
+    let second_result =
+        tokio::time::timeout(Duration::from_secs(5), second.start(second_shutdown_rx))
+            .await
+            .expect("Second watcher should fail immediately instead of running");
+    assert!(second_result.is_err(), "Second watcher should be rejected");
+
+    shutdown_tx
+        .send(())
+        .expect("Failed to send shutdown signal");
+    tokio::time::timeout(Duration::from_secs(5), first_handle)
+        .await
+        .expect("First watcher did not shut down")
+        .unwrap()
+        .expect("First watcher encountered an error");
+
+    // Once the first watcher has stopped, its directories can be watched again
+    let (third, _third_rx) =
+        HotReloadWatcher::with_root(AppState::new(), Config::default(), temp_dir.path());
+    let (third_shutdown_tx, third_shutdown_rx) = oneshot::channel();
+    third_shutdown_tx.send(()).unwrap();
+    assert!(third.start(third_shutdown_rx).await.is_ok());
+}
+
 /// Test that configurable debounce timing is respected
 #[tokio::test]
 async fn test_configurable_debounce_timing() {
@@ -182,15 +214,12 @@ async fn test_browser_reload_message() {
 /// Test that watcher handles non-existent directories gracefully
 #[tokio::test]
 async fn test_missing_directories_handling() {
+    // Temp directory without the expected subdirectories
     let temp_dir = TempDir::new().expect("Failed to create temp directory");
-    let original_dir = std::env::current_dir().expect("Failed to get current dir");
-
-    // Change to temp directory without creating expected subdirectories
-    std::env::set_current_dir(temp_dir.path()).expect("Failed to change directory");
 
     let state = AppState::new();
     let config = Config::default();
-    let (watcher, _reload_rx) = HotReloadWatcher::new(state, config);
+    let (watcher, _reload_rx) = HotReloadWatcher::with_root(state, config, temp_dir.path());
 
     // Create shutdown channel
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
@@ -209,11 +238,6 @@ async fn test_missing_directories_handling() {
     // Wait for watcher to shut down gracefully
     let result = tokio::time::timeout(Duration::from_secs(5), watcher_handle).await;
 
-    // Restore original directory before dropping temp_dir
-    // Use let _ = to ignore potential errors during test cleanup
-    let _ = std::env::set_current_dir(&original_dir);
-
-    // Now we can safely drop temp_dir
     drop(temp_dir);
 
     assert!(
@@ -0,0 +1,62 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::get_limits;
+use rust_mcp_server::shared::{config::Config, reload};
+
+async fn fetch_limits(config: Config) -> actix_web::dev::ServiceResponse {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(reload::shared(config)))
+            .route("/api/limits", web::get().to(get_limits)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/limits").to_request();
+    test::call_service(&app, req).await
+}
+
+fn limited_config() -> Config {
+    let mut config = Config::default();
+    config.server.enable_limits_reporting = true;
+    config.security.max_file_size_bytes = 2 * 1024 * 1024;
+    config.security.allowed_file_extensions = vec!["rs".to_string(), "toml".to_string()];
+    config.rate_limiting.requests_per_minute = 30;
+    config.rate_limiting.burst_size = 5;
+    config
+}
+
+/// Test that the endpoint reports the configured limits
+#[actix_web::test]
+async fn test_limits_endpoint_reports_configured_limits() {
+    let resp = fetch_limits(limited_config()).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    assert_eq!(body["max_file_size_bytes"], 2 * 1024 * 1024);
+    assert_eq!(body["restricted"], true);
+    assert_eq!(
+        body["allowed_file_extensions"],
+        serde_json::json!(["rs", "toml"])
+    );
+    assert_eq!(body["rate_limits"]["requests_per_minute"], 30);
+    assert_eq!(body["rate_limits"]["burst_size"], 5);
+}
+
+/// Test that redaction hides the allowlist but keeps the restricted flag
+#[actix_web::test]
+async fn test_limits_endpoint_redacts_allowlist() {
+    let mut config = limited_config();
+    config.server.redact_limits = true;
+
+    let resp = fetch_limits(config).await;
+    let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    assert_eq!(body["restricted"], true);
+    assert!(body["allowed_file_extensions"].is_null());
+    assert_eq!(body["max_file_size_bytes"], 2 * 1024 * 1024);
+}
+
+/// Test that the endpoint is hidden unless enabled
+#[actix_web::test]
+async fn test_limits_endpoint_disabled_by_default() {
+    let resp = fetch_limits(Config::default()).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,46 @@
+use mcp_server::ByteTransport;
+use rust_mcp_server::server::create_mcp_server;
+use rust_mcp_server::shared::config::Config;
+use rust_mcp_server::shared::state::{AppState, SystemEvent};
+use rust_mcp_server::shared::supervisor::track_mcp_connection;
+
+/// Test that the transport reaching EOF marks the server disconnected and emits the event
+#[tokio::test]
+async fn test_transport_eof_emits_disconnected() {
+    let state = AppState::new();
+    state.set_mcp_connected(true);
+    let server = create_mcp_server(state.clone(), Config::default())
+        .await
+        .unwrap();
+    // Subscribe after startup so the session start event is not seen first
+    let mut events = state.subscribe_to_events();
+
+    // An empty reader ends the transport immediately, like the client closing stdin
+    let transport = ByteTransport::new(tokio::io::empty(), tokio::io::sink());
+    track_mcp_connection(&state, server.run(transport))
+        .await
+        .unwrap();
+
+    assert!(!state.mcp_status.load().connected);
+    assert!(matches!(
+        events.try_recv(),
+        Ok(SystemEvent::McpDisconnected)
+    ));
+}
+
+/// Test that a failed session also reports the disconnect
+#[tokio::test]
+async fn test_failed_session_emits_disconnected() {
+    let state = AppState::new();
+    state.set_mcp_connected(true);
+    let mut events = state.subscribe_to_events();
+
+    let result = track_mcp_connection(&state, async { Err::<(), _>("transport error") }).await;
+
+    assert!(result.is_err());
+    assert!(!state.mcp_status.load().connected);
+    assert!(matches!(
+        events.try_recv(),
+        Ok(SystemEvent::McpDisconnected)
+    ));
+}
@@ -0,0 +1,35 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::get_openapi;
+
+/// Test that the spec is served and documents tool execution
+#[actix_web::test]
+async fn test_openapi_describes_execute_tool() {
+    let app =
+        test::init_service(App::new().route("/api/openapi.json", web::get().to(get_openapi))).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/openapi.json")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let spec: serde_json::Value = test::read_body_json(resp).await;
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3.0"));
+    assert_eq!(spec["info"]["version"], env!("CARGO_PKG_VERSION"));
+
+    let execute = &spec["paths"]["/api/tools/execute"]["post"];
+    assert!(execute.is_object());
+    assert!(execute["requestBody"]["content"]["application/json"]["schema"].is_object());
+    assert!(spec["paths"]["/api/tools/execute"]["get"].is_null());
+
+    for path in [
+        "/api/status",
+        "/api/metrics",
+        "/api/tools",
+        "/api/tool-calls",
+    ] {
+        assert!(spec["paths"][path]["get"].is_object(), "{path} is missing");
+    }
+    assert!(spec["components"]["schemas"]["ExecuteToolRequest"].is_object());
+    assert!(spec["components"]["schemas"]["ExecuteToolResponse"].is_object());
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "otel")]
+
+use mcp_server::router::Router;
+use opentelemetry::Value;
+use opentelemetry_sdk::export::trace::SpanData;
+use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::TracerProvider;
+use rust_mcp_server::server::mcp_router::McpRouter;
+use rust_mcp_server::shared::{state::AppState, telemetry};
+use tracing_subscriber::prelude::*;
+
+fn attribute(span: &SpanData, key: &str) -> Option<Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == key)
+        .map(|kv| kv.value.clone())
+}
+
+/// Test that every tool call produces a span with its name, duration and outcome
+#[tokio::test]
+async fn test_span_exported_per_tool_call() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(telemetry::layer(&provider)),
+    );
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let router = McpRouter::new(AppState::new());
+    router
+        .call_tool(
+            "file_search",
+            serde_json::json!({ "pattern": "x", "directory": temp_dir.path() }),
+        )
+        .await
+        .unwrap();
+    router
+        .call_tool("file_search", serde_json::json!({ "pattern": "(" }))
+        .await
+        .unwrap_err();
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let tool_spans: Vec<&SpanData> = spans.iter().filter(|s| s.name == "tool_call").collect();
+    assert_eq!(tool_spans.len(), 2);
+
+    for span in &tool_spans {
+        assert_eq!(
+            attribute(span, "tool.name"),
+            Some(Value::from("file_search"))
+        );
+        assert!(matches!(
+            attribute(span, "tool.duration_ms"),
+            Some(Value::I64(_))
+        ));
+    }
+    assert_eq!(
+        attribute(tool_spans[0], "tool.success"),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(
+        attribute(tool_spans[1], "tool.success"),
+        Some(Value::Bool(false))
+    );
+}
@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::prometheus_metrics;
+use rust_mcp_server::shared::state::{AppState, MetricValue};
+
+/// Test that counters, gauges and histograms are exported in Prometheus format
+#[actix_web::test]
+async fn test_prometheus_metrics_endpoint() {
+    let state = AppState::new();
+    state.update_metric("tool_calls_file_search", MetricValue::Counter(3));
+    state.update_metric("memory.usage-mb", MetricValue::Gauge(12.5));
+    state.update_metric(
+        "request_duration_seconds",
+        MetricValue::Histogram(vec![0.02, 0.3, 4.0, 20.0]),
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .route("/metrics", web::get().to(prometheus_metrics)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/plain; version=0.0.4"
+    );
+
+    let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    let lines: HashSet<&str> = body.lines().collect();
+    let expected: HashSet<&str> = [
+        "# TYPE tool_calls_file_search counter",
+        "tool_calls_file_search 3",
+        "# TYPE memory_usage_mb gauge",
+        "memory_usage_mb 12.5",
+        "# TYPE request_duration_seconds histogram",
+        "request_duration_seconds_bucket{le=\"0.005\"} 0",
+        "request_duration_seconds_bucket{le=\"0.01\"} 0",
+        "request_duration_seconds_bucket{le=\"0.025\"} 1",
+        "request_duration_seconds_bucket{le=\"0.05\"} 1",
+        "request_duration_seconds_bucket{le=\"0.1\"} 1",
+        "request_duration_seconds_bucket{le=\"0.25\"} 1",
+        "request_duration_seconds_bucket{le=\"0.5\"} 2",
+        "request_duration_seconds_bucket{le=\"1\"} 2",
+        "request_duration_seconds_bucket{le=\"2.5\"} 2",
+        "request_duration_seconds_bucket{le=\"5\"} 3",
+        "request_duration_seconds_bucket{le=\"10\"} 3",
+        "request_duration_seconds_bucket{le=\"+Inf\"} 4",
+        "request_duration_seconds_sum 24.32",
+        "request_duration_seconds_count 4",
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(lines, expected);
+}
+
+/// Test that per-tool structured counters are exported with their labels
+#[actix_web::test]
+async fn test_prometheus_metrics_labeled() {
+    use rust_mcp_server::shared::state::{MetricKey, ToolCall};
+
+    let state = AppState::new();
+    for name in ["file_search", "file_search", "detect_encoding"] {
+        let call = ToolCall::new(name.to_string(), serde_json::json!({}));
+        state.record_tool_call(call).await.unwrap();
+    }
+    state.increment_counter(
+        MetricKey::for_tool("file_search", "tool_errors").with_label("kind", "say \"io\""),
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .route("/metrics", web::get().to(prometheus_metrics)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = String::from_utf8(test::call_and_read_body(&app, req).await.to_vec()).unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+
+    assert_eq!(
+        lines
+            .iter()
+            .filter(|l| **l == "# TYPE tool_calls counter")
+            .count(),
+        1
+    );
+    assert!(lines.contains(&"tool_calls{tool=\"detect_encoding\"} 1"));
+    assert!(lines.contains(&"tool_calls{tool=\"file_search\"} 2"));
+    assert!(lines.contains(&"# TYPE tool_errors counter"));
+    assert!(lines.contains(&"tool_errors{tool=\"file_search\",kind=\"say \\\"io\\\"\"} 1"));
+}
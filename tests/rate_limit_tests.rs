@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use actix_web::http::{header, StatusCode};
+use actix_web::{middleware, test, web, App};
+use rust_mcp_server::dashboard::handlers::{get_status, health_check};
+use rust_mcp_server::dashboard::rate_limit::{rate_limit, HttpRateLimiter};
+use rust_mcp_server::shared::{config::Config, reload, state::AppState};
+
+fn limited_config() -> Config {
+    let mut config = Config::default();
+    config.rate_limiting.enabled = true;
+    config.rate_limiting.requests_per_minute = 600;
+    config.rate_limiting.burst_size = 3;
+    config
+}
+
+/// Test that a client past the burst gets 429 with Retry-After and recovers once it elapses
+#[actix_web::test]
+async fn test_status_is_rate_limited_then_recovers() {
+    let config = limited_config();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new()))
+            .app_data(web::Data::new(config.clone()))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(HttpRateLimiter::new()))
+            .wrap(middleware::from_fn(rate_limit))
+            .route("/api/status", web::get().to(get_status)),
+    )
+    .await;
+
+    let status_request = || {
+        test::TestRequest::get()
+            .uri("/api/status")
+            .peer_addr("192.0.2.10:40000".parse().unwrap())
+            .to_request()
+    };
+
+    let mut retry_after = None;
+    for _ in 0..10 {
+        let resp = test::call_service(&app, status_request()).await;
+        if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+            retry_after = resp
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok());
+            break;
+        }
+        assert!(resp.status().is_success());
+    }
+    let retry_after = retry_after.expect("expected a 429 with a Retry-After header");
+    assert!(retry_after >= 1);
+
+    // Another client is unaffected
+    let req = test::TestRequest::get()
+        .uri("/api/status")
+        .peer_addr("192.0.2.11:40000".parse().unwrap())
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    tokio::time::sleep(Duration::from_secs(retry_after)).await;
+    let resp = test::call_service(&app, status_request()).await;
+    assert!(resp.status().is_success());
+}
+
+/// Test that /health is never rate limited
+#[actix_web::test]
+async fn test_health_is_exempt() {
+    let config = limited_config();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new()))
+            .app_data(web::Data::new(config.clone()))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(HttpRateLimiter::new()))
+            .wrap(middleware::from_fn(rate_limit))
+            .route("/health", web::get().to(health_check)),
+    )
+    .await;
+
+    for _ in 0..20 {
+        let req = test::TestRequest::get()
+            .uri("/health")
+            .peer_addr("192.0.2.10:40000".parse().unwrap())
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+    }
+}
+
+/// Test that the limiter is a no-op unless enabled
+#[actix_web::test]
+async fn test_disabled_by_default() {
+    let config = Config::default();
+    assert!(!config.rate_limiting.enabled);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new()))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(HttpRateLimiter::new()))
+            .wrap(middleware::from_fn(rate_limit))
+            .route("/api/status", web::get().to(get_status)),
+    )
+    .await;
+
+    for _ in 0..20 {
+        let req = test::TestRequest::get()
+            .uri("/api/status")
+            .peer_addr("192.0.2.10:40000".parse().unwrap())
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+    }
+}
@@ -0,0 +1,44 @@
+use actix_web::{middleware, test, web, App};
+use rust_mcp_server::dashboard::{handlers::execute_tool, server::request_id};
+use rust_mcp_server::shared::state::AppState;
+
+/// Test that each response carries an `X-Request-Id`, echoing the caller's if sent
+#[actix_web::test]
+async fn test_request_id_header_assigned_and_propagated() {
+    let state = AppState::new();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .wrap(middleware::from_fn(request_id))
+            .route("/api/tools/execute", web::post().to(execute_tool)),
+    )
+    .await;
+    let body = serde_json::json!({ "name": "file_search", "arguments": { "query": "main" } });
+
+    let req = test::TestRequest::post()
+        .uri("/api/tools/execute")
+        .set_json(&body)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let generated = resp
+        .headers()
+        .get("x-request-id")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(uuid::Uuid::parse_str(generated).is_ok());
+
+    let req = test::TestRequest::post()
+        .uri("/api/tools/execute")
+        .insert_header(("x-request-id", "req-1234"))
+        .set_json(&body)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "req-1234");
+
+    // The recorded tool call carries the id of the request that made it
+    let calls = state.tool_calls.read().await;
+    assert!(calls
+        .iter()
+        .any(|call| call.request_id.as_deref() == Some("req-1234")));
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use actix_web::body::MessageBody;
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::websocket::sse_handler;
+use rust_mcp_server::shared::{
+    config::Config,
+    state::{AppState, SystemEvent},
+};
+
+/// Test that `?types=` limits the SSE stream to the requested event types
+#[actix_web::test]
+async fn test_sse_streams_only_requested_types() {
+    let state = AppState::new();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .app_data(web::Data::new(Config::default()))
+            .route("/sse", web::get().to(sse_handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/sse?types=error,not_a_type")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    state
+        .event_tx
+        .send(SystemEvent::ToolCalled {
+            name: "file_search".to_string(),
+            id: uuid::Uuid::new_v4(),
+        })
+        .unwrap();
+    state
+        .event_tx
+        .send(SystemEvent::Error {
+            message: "disk full".to_string(),
+        })
+        .unwrap();
+
+    let mut body = Box::pin(resp.into_body());
+    let chunk = tokio::time::timeout(
+        Duration::from_secs(2),
+        std::future::poll_fn(|cx| body.as_mut().poll_next(cx)),
+    )
+    .await
+    .expect("an event should be streamed")
+    .expect("stream should not end")
+    .unwrap();
+
+    let frame = String::from_utf8(chunk.to_vec()).unwrap();
+    assert!(
+        frame.starts_with("event: error\n"),
+        "unexpected frame: {frame}"
+    );
+    assert!(frame.contains("disk full"));
+}
@@ -0,0 +1,42 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::{get_status, get_status_json};
+use rust_mcp_server::shared::{
+    config::Config,
+    state::{AppState, ToolCall},
+};
+
+/// Test that `/api/status.json` returns the status as JSON alongside the HTML fragment
+#[actix_web::test]
+async fn test_status_json() {
+    let state = AppState::new();
+    state
+        .add_tool_call(ToolCall::new(
+            "file_search".to_string(),
+            serde_json::json!({}),
+        ))
+        .await;
+    state.start_session();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .app_data(web::Data::new(Config::default()))
+            .route("/api/status", web::get().to(get_status))
+            .route("/api/status.json", web::get().to(get_status_json)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/status.json")
+        .to_request();
+    let status: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(status["connected"], true);
+    assert_eq!(status["server_info"]["name"], "Rust MCP Server");
+    assert_eq!(status["active_sessions"], 1);
+    assert_eq!(status["total_tool_calls"], 1);
+
+    // The htmx fragment is unchanged
+    let req = test::TestRequest::get().uri("/api/status").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("content-type").unwrap(), "text/html");
+}
@@ -0,0 +1,90 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::{get_events, get_sessions, health_check};
+use rust_mcp_server::shared::config::{Config, TimestampFormat};
+use rust_mcp_server::shared::state::{AppState, SessionInfo, SystemEvent};
+use uuid::Uuid;
+
+async fn fetch_json(config: Config, state: AppState, uri: &str) -> serde_json::Value {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(state))
+            .route("/health", web::get().to(health_check))
+            .route("/api/events", web::get().to(get_events))
+            .route("/api/sessions", web::get().to(get_sessions)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri(uri).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    serde_json::from_slice(&test::read_body(resp).await).unwrap()
+}
+
+fn state_with_session() -> AppState {
+    let state = AppState::new();
+    let id = Uuid::new_v4();
+    state.active_sessions.insert(
+        id,
+        SessionInfo {
+            id,
+            started_at: chrono::Utc::now(),
+            last_activity: chrono::Utc::now(),
+            request_count: 1,
+        },
+    );
+    state
+}
+
+async fn state_with_event() -> AppState {
+    let state = AppState::new().with_event_history(10);
+    state.event_tx.send(SystemEvent::McpConnected).unwrap();
+    for _ in 0..100 {
+        if !state.get_events(1).await.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    state
+}
+
+fn assert_rfc3339(value: &serde_json::Value) {
+    let text = value.as_str().expect("timestamp should be a string");
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(text).is_ok(),
+        "{text} is not RFC3339"
+    );
+}
+
+/// Test that JSON API timestamps are RFC3339 by default
+#[actix_web::test]
+async fn test_api_timestamps_are_rfc3339() {
+    let health = fetch_json(Config::default(), AppState::new(), "/health").await;
+    assert_rfc3339(&health["timestamp"]);
+
+    let events = fetch_json(Config::default(), state_with_event().await, "/api/events").await;
+    assert_rfc3339(&events[0]["timestamp"]);
+    assert_eq!(events[0]["message"], "MCP server connected");
+
+    let sessions = fetch_json(Config::default(), state_with_session(), "/api/sessions").await;
+    assert_rfc3339(&sessions[0]["started_at"]);
+    assert_rfc3339(&sessions[0]["last_activity"]);
+}
+
+/// Test that epoch-millis output can be selected for machine consumers
+#[actix_web::test]
+async fn test_api_timestamps_epoch_millis() {
+    let mut config = Config::default();
+    config.server.timestamp_format = TimestampFormat::EpochMillis;
+
+    let before = chrono::Utc::now().timestamp_millis();
+    let health = fetch_json(config.clone(), AppState::new(), "/health").await;
+    let millis = health["timestamp"]
+        .as_i64()
+        .expect("timestamp should be a number");
+    assert!(millis >= before);
+
+    let sessions = fetch_json(config, state_with_session(), "/api/sessions").await;
+    assert!(sessions[0]["started_at"].is_i64());
+}
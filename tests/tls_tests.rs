@@ -0,0 +1,61 @@
+use rust_mcp_server::dashboard::server::run_dashboard_with_config;
+use rust_mcp_server::shared::config::Config;
+use rust_mcp_server::shared::state::AppState;
+
+const CERT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls-cert.pem");
+const KEY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls-key.pem");
+
+/// Test that setting only one of the TLS paths fails validation
+#[test]
+fn test_tls_paths_must_be_set_together() {
+    let mut config = Config::default();
+    config.server.tls_cert_path = Some(CERT.to_string());
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("TLS_KEY_PATH"), "{err}");
+
+    let mut config = Config::default();
+    config.server.tls_key_path = Some(KEY.to_string());
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("TLS_CERT_PATH"), "{err}");
+
+    let mut config = Config::default();
+    config.server.tls_cert_path = Some(CERT.to_string());
+    config.server.tls_key_path = Some(KEY.to_string());
+    assert!(config.validate().is_ok());
+}
+
+/// Test that the dashboard serves `/health` over HTTPS with a self-signed certificate
+#[actix_web::test]
+async fn test_health_over_https() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+
+    let mut config = Config::default();
+    config.server.dashboard_host = "127.0.0.1".to_string();
+    config.server.dashboard_port = port;
+    config.server.tls_cert_path = Some(CERT.to_string());
+    config.server.tls_key_path = Some(KEY.to_string());
+    let state = AppState::new();
+    state.set_mcp_connected(true);
+    actix_web::rt::spawn(run_dashboard_with_config(state, config, false));
+
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let url = format!("https://127.0.0.1:{port}/health");
+
+    let mut response = None;
+    for _ in 0..50 {
+        if let Ok(r) = client.get(&url).send().await {
+            response = Some(r);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let response = response.expect("dashboard did not accept HTTPS connections");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+}
@@ -0,0 +1,229 @@
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::{clear_tool_calls, get_tool_calls};
+use rust_mcp_server::shared::{
+    config::Config,
+    state::{AppState, MetricValue, ToolCall, ToolCallResult},
+};
+
+fn debug_config() -> Config {
+    let mut config = Config::default();
+    config.development.enable_debug_routes = true;
+    config
+}
+
+/// Test that DELETE empties the history shown by the tool calls view
+#[actix_web::test]
+async fn test_clear_tool_calls() {
+    let state = AppState::new();
+    let call = ToolCall::new("file_search".to_string(), serde_json::json!({}));
+    state.record_tool_call(call).await.unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .app_data(web::Data::new(debug_config()))
+            .route("/api/tool-calls", web::get().to(get_tool_calls))
+            .route("/api/tool-calls", web::delete().to(clear_tool_calls)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/tool-calls").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    assert!(String::from_utf8_lossy(&body).contains("file_search"));
+
+    let req = test::TestRequest::delete()
+        .uri("/api/tool-calls")
+        .to_request();
+    let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(resp["cleared"], 1);
+    assert_eq!(resp["metrics_reset"], false);
+
+    let req = test::TestRequest::get().uri("/api/tool-calls").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    assert!(!String::from_utf8_lossy(&body).contains("file_search"));
+    assert!(state.get_tool_calls(10).await.is_empty());
+
+    // Counters survive unless a reset is requested
+    assert!(matches!(
+        state.get_metrics().await.get("tool_calls_file_search"),
+        Some(MetricValue::Counter(1))
+    ));
+    let req = test::TestRequest::delete()
+        .uri("/api/tool-calls?reset_metrics=true")
+        .to_request();
+    let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(resp["cleared"], 0);
+    assert!(!state
+        .get_metrics()
+        .await
+        .contains_key("tool_calls_file_search"));
+    assert!(state.tool_metrics("file_search").is_empty());
+}
+
+/// Test that clearing is unavailable without debug routes
+#[actix_web::test]
+async fn test_clear_tool_calls_requires_debug_routes() {
+    let state = AppState::new();
+    let call = ToolCall::new("file_search".to_string(), serde_json::json!({}));
+    state.record_tool_call(call).await.unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .app_data(web::Data::new(Config::default()))
+            .route("/api/tool-calls", web::delete().to(clear_tool_calls)),
+    )
+    .await;
+
+    let req = test::TestRequest::delete()
+        .uri("/api/tool-calls")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(state.get_tool_calls(10).await.len(), 1);
+}
+
+/// Test that limit and offset page through the history newest first
+#[actix_web::test]
+async fn test_tool_calls_pagination() {
+    let state = AppState::new();
+    for i in 0..50 {
+        let call = ToolCall::new(format!("tool_{i:02}"), serde_json::json!({}));
+        state.record_tool_call(call).await.unwrap();
+    }
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .route("/api/tool-calls", web::get().to(get_tool_calls)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tool-calls?limit=10&offset=10")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(resp.headers().get("X-Total-Count").unwrap(), "50");
+
+    let body = String::from_utf8_lossy(&test::read_body(resp).await).to_string();
+    let shown: Vec<usize> = (0..50)
+        .filter(|i| body.contains(&format!("tool_{i:02}</span>")))
+        .collect();
+    assert_eq!(shown, (30..40).collect::<Vec<_>>());
+    assert!(body.contains("of 50"));
+
+    // The default page is the 20 newest calls
+    let req = test::TestRequest::get().uri("/api/tool-calls").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains("tool_49</span>"));
+    assert!(body.contains("tool_30</span>"));
+    assert!(!body.contains("tool_29</span>"));
+
+    for uri in [
+        "/api/tool-calls?limit=0",
+        "/api/tool-calls?limit=1000",
+        "/api/tool-calls?limit=ten",
+        "/api/tool-calls?offset=-1",
+    ] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{uri}");
+    }
+}
+
+/// Test that tool and status filters compose and apply before paging
+#[actix_web::test]
+async fn test_tool_calls_filter_by_tool_and_status() {
+    let state = AppState::new();
+    for i in 0..12 {
+        let name = if i % 2 == 0 {
+            "http_get"
+        } else {
+            "file_search"
+        };
+        let result = if i % 3 == 0 {
+            ToolCallResult::Error(format!("failure {i}"))
+        } else {
+            ToolCallResult::Success(serde_json::json!({ "run": i }))
+        };
+        let call = ToolCall::new(name.to_string(), serde_json::json!({}));
+        state
+            .record_tool_call(call.complete(result, 5))
+            .await
+            .unwrap();
+    }
+    // A pending call is neither a success nor an error
+    let pending = ToolCall::new("http_get".to_string(), serde_json::json!({}));
+    state.record_tool_call(pending).await.unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .route("/api/tool-calls", web::get().to(get_tool_calls)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tool-calls?tool=http_get&status=error")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    // Runs 0 and 6 are the failed http_get calls
+    assert_eq!(resp.headers().get("X-Total-Count").unwrap(), "2");
+    let body = String::from_utf8_lossy(&test::read_body(resp).await).to_string();
+    assert!(body.contains("failure 0"));
+    assert!(body.contains("failure 6"));
+    assert!(!body.contains("failure 3"));
+    assert!(!body.contains("file_search"));
+
+    let req = test::TestRequest::get()
+        .uri("/api/tool-calls?tool=http_get&status=error&limit=1&offset=1")
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains("failure 0"));
+    assert!(!body.contains("failure 6"));
+
+    let req = test::TestRequest::get()
+        .uri("/api/tool-calls?status=success")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("X-Total-Count").unwrap(), "8");
+
+    let req = test::TestRequest::get()
+        .uri("/api/tool-calls?status=flaky")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+/// Test that a registry with state records calls made directly through it
+#[tokio::test]
+async fn test_registry_with_state_records_file_search() {
+    use rust_mcp_server::tools::{file_search::FileSearchTool, ToolRegistry};
+
+    let state = AppState::new();
+    let mut registry = ToolRegistry::new().with_state(state.clone());
+    registry.register(FileSearchTool::default());
+    let temp_dir = tempfile::TempDir::new().unwrap();
+
+    registry
+        .call_tool(
+            "file_search",
+            serde_json::json!({ "pattern": "x", "directory": temp_dir.path() }),
+        )
+        .await
+        .unwrap();
+
+    let calls = state.tool_calls.read().await;
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].name, "file_search");
+    assert!(matches!(calls[0].result, Some(ToolCallResult::Success(_))));
+    assert!(matches!(
+        state.metrics.get("tool_calls_file_search").as_deref(),
+        Some(MetricValue::Counter(1))
+    ));
+}
@@ -0,0 +1,238 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use rust_mcp_server::shared::state::{AppState, ToolCall, ToolCallResult};
+use tempfile::TempDir;
+
+/// Wait for the background writer to flush `expected` lines to the log
+async fn wait_for_lines(path: &Path, expected: usize) {
+    for _ in 0..100 {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        if contents.lines().filter(|l| !l.trim().is_empty()).count() >= expected {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("tool call log never reached {expected} lines");
+}
+
+fn completed_call(name: &str) -> ToolCall {
+    ToolCall::new(name.to_string(), serde_json::json!({ "pattern": "x" })).complete(
+        ToolCallResult::Success(serde_json::json!({ "ok": true })),
+        5,
+    )
+}
+
+/// Test that recorded calls survive a restart and corrupt lines are skipped
+#[tokio::test]
+async fn test_tool_calls_replayed_after_restart() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("tool_calls.jsonl");
+
+    let state = AppState::with_persistence(&log_path).await.unwrap();
+    for name in ["first", "second", "third"] {
+        state.record_tool_call(completed_call(name)).await.unwrap();
+    }
+    wait_for_lines(&log_path, 3).await;
+    drop(state);
+
+    // Simulate a crash mid-write
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&log_path)
+        .unwrap();
+    file.write_all(br#"{"id":"d3b07384-d9a0-4c"#).unwrap();
+    drop(file);
+
+    let restarted = AppState::with_persistence(&log_path).await.unwrap();
+    let names: Vec<String> = restarted
+        .tool_calls
+        .read()
+        .await
+        .iter()
+        .map(|c| c.name.clone())
+        .collect();
+    assert_eq!(names, vec!["first", "second", "third"]);
+
+    // New records are appended on their own line after the partial one
+    restarted
+        .record_tool_call(completed_call("fourth"))
+        .await
+        .unwrap();
+    wait_for_lines(&log_path, 5).await;
+
+    let replayed = AppState::with_persistence(&log_path).await.unwrap();
+    let calls = replayed.tool_calls.read().await;
+    assert_eq!(calls.len(), 4);
+    assert_eq!(calls[3].name, "fourth");
+    assert!(calls[3].success);
+}
+
+/// Test that only the most recent 1000 calls are replayed
+#[tokio::test]
+async fn test_replay_keeps_last_1000_calls() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("tool_calls.jsonl");
+
+    let mut contents = String::new();
+    for i in 0..1005 {
+        contents.push_str(&serde_json::to_string(&completed_call(&format!("call_{i}"))).unwrap());
+        contents.push('\n');
+    }
+    std::fs::write(&log_path, contents).unwrap();
+
+    let state = AppState::with_persistence(&log_path).await.unwrap();
+    let calls = state.tool_calls.read().await;
+    assert_eq!(calls.len(), 1000);
+    assert_eq!(calls[0].name, "call_5");
+    assert_eq!(calls[999].name, "call_1004");
+}
+
+/// Test that calls logged as pending and then completed replay as one entry each
+#[tokio::test]
+async fn test_replay_merges_pending_and_completed_records() {
+    use rust_mcp_server::tools::{file_search::FileSearchTool, ToolRegistry};
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("tool_calls.jsonl");
+
+    let state = AppState::with_persistence(&log_path).await.unwrap();
+    let mut registry = ToolRegistry::new().with_state(state.clone());
+    registry.register(FileSearchTool::default());
+    // Search elsewhere so results do not quote the log itself
+    let search_dir = TempDir::new().unwrap();
+    for _ in 0..3 {
+        let call = ToolCall::new(
+            "file_search".to_string(),
+            serde_json::json!({ "pattern": "x", "directory": search_dir.path() }),
+        );
+        registry.execute(call).await.unwrap();
+    }
+    // Each call is logged twice: when it starts and when it completes
+    wait_for_lines(&log_path, 6).await;
+    drop(registry);
+    drop(state);
+
+    let replayed = AppState::with_persistence(&log_path).await.unwrap();
+    let calls = replayed.tool_calls.read().await;
+    assert_eq!(calls.len(), 3);
+    assert!(calls
+        .iter()
+        .all(|call| matches!(call.result, Some(ToolCallResult::Success(_)))));
+    let ids: std::collections::HashSet<_> = calls.iter().map(|call| call.id).collect();
+    assert_eq!(ids.len(), 3);
+}
+
+/// Decompress a gzip-segmented log, tolerating a segment still being written
+fn decompressed_line_count(path: &Path) -> usize {
+    use std::io::Read;
+
+    let raw = std::fs::read(path).unwrap_or_default();
+    let mut contents = String::new();
+    let _ = flate2::read::MultiGzDecoder::new(raw.as_slice()).read_to_string(&mut contents);
+    contents.lines().filter(|l| !l.trim().is_empty()).count()
+}
+
+/// Test that compressed logs load back correctly and are smaller than plain ones
+#[tokio::test]
+async fn test_compressed_tool_call_log() {
+    let temp_dir = TempDir::new().unwrap();
+    let plain_path = temp_dir.path().join("plain.jsonl");
+    let compressed_path = temp_dir.path().join("compressed.jsonl.gz");
+
+    let plain = AppState::with_persistence(&plain_path).await.unwrap();
+    let compressed = AppState::with_compressed_persistence(&compressed_path)
+        .await
+        .unwrap();
+    for i in 0..500 {
+        let call = completed_call(&format!("call_{i}"));
+        plain.record_tool_call(call.clone()).await.unwrap();
+        compressed.record_tool_call(call).await.unwrap();
+    }
+
+    wait_for_lines(&plain_path, 500).await;
+    for _ in 0..100 {
+        if decompressed_line_count(&compressed_path) >= 500 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(decompressed_line_count(&compressed_path), 500);
+    drop(compressed);
+
+    let plain_size = std::fs::metadata(&plain_path).unwrap().len();
+    let compressed_size = std::fs::metadata(&compressed_path).unwrap().len();
+    assert!(
+        compressed_size < plain_size,
+        "compressed log ({compressed_size} bytes) should be smaller than plain ({plain_size} bytes)"
+    );
+
+    let reloaded = AppState::with_compressed_persistence(&compressed_path)
+        .await
+        .unwrap();
+    let calls = reloaded.tool_calls.read().await;
+    assert_eq!(calls.len(), 500);
+    assert_eq!(calls[0].name, "call_0");
+    assert_eq!(calls[499].name, "call_499");
+}
+
+/// Compress newline-terminated records as one gzip segment
+fn gzip_segment(calls: &[&str]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    for name in calls {
+        let line = serde_json::to_string(&completed_call(name)).unwrap();
+        writeln!(encoder, "{line}").unwrap();
+    }
+    encoder.finish().unwrap()
+}
+
+/// Test that a corrupt gzip segment is skipped without losing the records
+/// around it, and that a broken trailing segment is cut off before appending
+#[tokio::test]
+async fn test_compressed_log_skips_corrupt_segments() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("tool_calls.jsonl.gz");
+
+    let mut corrupt = gzip_segment(&["lost_1", "lost_2"]);
+    let middle = corrupt.len() / 2;
+    corrupt[middle] ^= 0xff;
+    corrupt[middle + 1] ^= 0xff;
+    let truncated = gzip_segment(&["partial"]);
+
+    let mut contents = gzip_segment(&["first"]);
+    contents.extend(corrupt);
+    contents.extend(gzip_segment(&["second"]));
+    let readable_len = contents.len() as u64;
+    contents.extend(&truncated[..truncated.len() - 8]);
+    std::fs::write(&log_path, contents).unwrap();
+
+    let state = AppState::with_compressed_persistence(&log_path)
+        .await
+        .unwrap();
+    {
+        let calls = state.tool_calls.read().await;
+        let names: Vec<_> = calls.iter().map(|call| call.name.as_str()).collect();
+        assert_eq!(&names[..2], ["first", "second"]);
+    }
+    assert_eq!(std::fs::metadata(&log_path).unwrap().len(), readable_len);
+
+    state
+        .record_tool_call(completed_call("third"))
+        .await
+        .unwrap();
+    for _ in 0..100 {
+        if std::fs::metadata(&log_path).unwrap().len() > readable_len {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    drop(state);
+
+    let reloaded = AppState::with_compressed_persistence(&log_path)
+        .await
+        .unwrap();
+    let calls = reloaded.tool_calls.read().await;
+    let names: Vec<_> = calls.iter().map(|call| call.name.as_str()).collect();
+    assert_eq!(names, ["first", "second", "third"]);
+}
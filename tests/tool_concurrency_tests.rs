@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::execute_tool;
+use rust_mcp_server::shared::state::AppState;
+
+/// Test that the dashboard asks clients to back off when every tool slot is busy
+#[actix_web::test]
+async fn test_execute_tool_rejected_when_slots_busy() {
+    let state = AppState::new().with_tool_concurrency(1, Duration::from_millis(20));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .route("/api/tools/execute", web::post().to(execute_tool)),
+    )
+    .await;
+    let request = || {
+        test::TestRequest::post()
+            .uri("/api/tools/execute")
+            .set_json(serde_json::json!({
+                "name": "file_search",
+                "arguments": { "query": "needle" }
+            }))
+            .to_request()
+    };
+
+    let busy = state.acquire_tool_permit("file_search").await.unwrap();
+    let resp = test::call_service(&app, request()).await;
+    assert_eq!(
+        resp.status(),
+        actix_web::http::StatusCode::TOO_MANY_REQUESTS
+    );
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error_code"], "RATE_LIMIT");
+    assert!(state.tool_calls.read().await.is_empty());
+
+    drop(busy);
+    let resp = test::call_service(&app, request()).await;
+    assert!(resp.status().is_success());
+}
+
+/// Test that failures carry a machine-readable error code
+#[actix_web::test]
+async fn test_execute_tool_error_codes() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new()))
+            .route("/api/tools/execute", web::post().to(execute_tool)),
+    )
+    .await;
+
+    for (arguments, name, code) in [
+        (serde_json::json!({}), "file_search", "INVALID_INPUT"),
+        (serde_json::json!({}), "no_such_tool", "TOOL_NOT_FOUND"),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/api/tools/execute")
+            .set_json(serde_json::json!({ "name": name, "arguments": arguments }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["error_code"], code);
+        assert!(body["error"].is_string());
+    }
+}
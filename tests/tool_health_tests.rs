@@ -0,0 +1,87 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::get_tool_health;
+use rust_mcp_server::shared::config::Config;
+use rust_mcp_server::shared::state::{AppState, ToolCall, ToolCallResult};
+
+/// Test that the last success and last error of a tool are both reported
+#[actix_web::test]
+async fn test_tool_health_reports_last_success_and_error() {
+    let state = AppState::new();
+
+    let success = ToolCall::new("file_search".to_string(), serde_json::json!({})).complete(
+        ToolCallResult::Success(serde_json::json!({ "ok": true })),
+        10,
+    );
+    let success_at = success.timestamp + chrono::Duration::milliseconds(10);
+    state.record_tool_call(success).await.unwrap();
+
+    let failure = ToolCall::new("file_search".to_string(), serde_json::json!({})).complete(
+        ToolCallResult::Error("No space left on device".to_string()),
+        20,
+    );
+    let failure_at = failure.timestamp + chrono::Duration::milliseconds(20);
+    state.record_tool_call(failure).await.unwrap();
+
+    // A call that has not completed yet does not count either way
+    state
+        .record_tool_call(ToolCall::new(
+            "detect_encoding".to_string(),
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+
+    let mut config = Config::default();
+    config.server.enable_tool_health = true;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(state))
+            .route("/api/tools/health", web::get().to(get_tool_health)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tools/health")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    let tools = body.as_array().unwrap();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0]["name"], "file_search");
+
+    let parse = |value: &serde_json::Value| {
+        chrono::DateTime::parse_from_rfc3339(value.as_str().unwrap())
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    };
+    assert_eq!(
+        parse(&tools[0]["last_success"]).timestamp_millis(),
+        success_at.timestamp_millis()
+    );
+    assert_eq!(
+        parse(&tools[0]["last_error"]).timestamp_millis(),
+        failure_at.timestamp_millis()
+    );
+    assert_eq!(tools[0]["last_error_message"], "No space left on device");
+}
+
+/// Test that the endpoint is hidden unless enabled
+#[actix_web::test]
+async fn test_tool_health_disabled_by_default() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(Config::default()))
+            .app_data(web::Data::new(AppState::new()))
+            .route("/api/tools/health", web::get().to(get_tool_health)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tools/health")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+}
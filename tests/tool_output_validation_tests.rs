@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use rust_mcp_server::server::error::ToolError;
+use rust_mcp_server::tools::{McpTool, ToolRegistry};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
+
+#[derive(Deserialize, JsonSchema)]
+struct CountInput {}
+
+/// Declares an integer `count` but serializes it as a string
+#[derive(JsonSchema)]
+struct CountOutput {
+    #[allow(dead_code)]
+    count: u32,
+}
+
+impl Serialize for CountOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_json::json!({ "count": "many" }).serialize(serializer)
+    }
+}
+
+struct BrokenCountTool;
+
+#[async_trait]
+impl McpTool for BrokenCountTool {
+    type Input = CountInput;
+    type Output = CountOutput;
+
+    fn name(&self) -> &'static str {
+        "broken_count"
+    }
+
+    fn description(&self) -> &'static str {
+        "Returns output that violates its own schema"
+    }
+
+    async fn execute(&self, _input: Self::Input) -> Result<Self::Output, ToolError> {
+        Ok(CountOutput { count: 3 })
+    }
+}
+
+fn registry(validate_output: bool) -> ToolRegistry {
+    let mut registry = ToolRegistry::new();
+    registry.register(BrokenCountTool);
+    registry.with_output_validation(validate_output)
+}
+
+/// Test that schema-violating output is caught only when validation is enabled
+#[tokio::test]
+async fn test_output_validation_catches_schema_violation() {
+    let unchecked = registry(false)
+        .call_tool("broken_count", serde_json::json!({}))
+        .await
+        .unwrap();
+    assert_eq!(unchecked["count"], "many");
+
+    let checked = registry(true)
+        .call_tool("broken_count", serde_json::json!({}))
+        .await;
+    match checked {
+        Err(ToolError::SerializationError(message)) => {
+            assert!(message.contains("broken_count"), "{message}");
+            assert!(message.contains("/count"), "{message}");
+        }
+        other => panic!("expected a schema mismatch, got {other:?}"),
+    }
+}
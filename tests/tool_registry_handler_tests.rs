@@ -0,0 +1,91 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::{get_metrics, get_tool_schema, list_tools};
+use rust_mcp_server::shared::config::Config;
+use rust_mcp_server::shared::state::AppState;
+use rust_mcp_server::tools::{
+    detect_encoding::DetectEncodingTool, file_search::FileSearchTool, ToolRegistry,
+};
+
+fn two_tool_registry() -> ToolRegistry {
+    let mut registry = ToolRegistry::new();
+    registry.register(FileSearchTool::default());
+    registry.register(DetectEncodingTool::new(&Config::default().security));
+    registry
+}
+
+async fn fetch_html(uri: &str) -> String {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new()))
+            .app_data(web::Data::new(Config::default()))
+            .app_data(web::Data::new(two_tool_registry()))
+            .route("/api/metrics", web::get().to(get_metrics))
+            .route("/api/tools", web::get().to(list_tools)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri(uri).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    String::from_utf8(test::read_body(resp).await.to_vec()).unwrap()
+}
+
+/// Test that the metrics fragment reports the registry's tool count
+#[actix_web::test]
+async fn test_metrics_report_registered_tool_count() {
+    let body = fetch_html("/api/metrics").await;
+    assert!(
+        body.contains("text-teal-300 mb-2\">2</div>"),
+        "expected tools_available == 2 in:\n{body}"
+    );
+}
+
+/// Test that the metrics fragment reports the resources the MCP router serves
+#[actix_web::test]
+async fn test_metrics_report_resource_count() {
+    let body = fetch_html("/api/metrics").await;
+    let expected = rust_mcp_server::server::mcp_router::resource_count(&Config::default());
+    // The config and recent logs resources are always served
+    assert_eq!(expected, 2);
+    assert!(
+        body.contains(&format!("text-orange-300 mb-2\">{expected}</div>")),
+        "expected resources_available == {expected} in:\n{body}"
+    );
+}
+
+/// Test that the tools fragment lists every registered tool
+#[actix_web::test]
+async fn test_tools_listed_from_registry() {
+    let body = fetch_html("/api/tools").await;
+    assert!(body.contains("file_search"));
+    assert!(body.contains("detect_encoding"));
+    assert!(body.contains("filesystem"));
+}
+
+/// Test that a tool's schemas are served by name, and unknown tools are a 404
+#[actix_web::test]
+async fn test_tool_schema_endpoint() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(two_tool_registry()))
+            .route("/api/tools/{name}/schema", web::get().to(get_tool_schema)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tools/file_search/schema")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert!(body["input_schema"]["properties"]["pattern"].is_object());
+    assert!(body["output_schema"]["properties"]["matches"].is_object());
+
+    let req = test::TestRequest::get()
+        .uri("/api/tools/no_such_tool/schema")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+}
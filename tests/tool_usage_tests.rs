@@ -0,0 +1,76 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::get_tool_usage;
+use rust_mcp_server::shared::config::Config;
+use rust_mcp_server::shared::state::{AppState, ToolCall, ToolCallResult};
+
+async fn record(state: &AppState, name: &str, success: bool, duration_ms: u64) {
+    let result = if success {
+        ToolCallResult::Success(serde_json::json!({}))
+    } else {
+        ToolCallResult::Error("failed".to_string())
+    };
+    let call = ToolCall::new(name.to_string(), serde_json::json!({})).complete(result, duration_ms);
+    state.record_tool_call(call).await.unwrap();
+}
+
+async fn fetch_usage(config: Config, state: AppState) -> actix_web::dev::ServiceResponse {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(state))
+            .route("/api/tools/usage", web::get().to(get_tool_usage)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/tools/usage")
+        .to_request();
+    test::call_service(&app, req).await
+}
+
+/// Test that tools are ranked by call count with success rate and average duration
+#[actix_web::test]
+async fn test_tool_usage_leaderboard() {
+    let state = AppState::new();
+    for duration in [10, 20, 30] {
+        record(&state, "file_search", true, duration).await;
+    }
+    record(&state, "file_search", false, 40).await;
+    record(&state, "detect_encoding", true, 5).await;
+    for _ in 0..2 {
+        record(&state, "word_count", true, 1).await;
+    }
+    // In-flight calls are not counted
+    state
+        .record_tool_call(ToolCall::new(
+            "detect_encoding".to_string(),
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+
+    let mut config = Config::default();
+    config.server.enable_tool_usage = true;
+    let resp = fetch_usage(config, state).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    let usage = body.as_array().unwrap();
+    let names: Vec<&str> = usage.iter().map(|u| u["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["file_search", "word_count", "detect_encoding"]);
+
+    assert_eq!(usage[0]["calls"], 4);
+    assert_eq!(usage[0]["success_rate"], 75.0);
+    assert_eq!(usage[0]["avg_duration_ms"], 25.0);
+    assert_eq!(usage[1]["calls"], 2);
+    assert_eq!(usage[1]["success_rate"], 100.0);
+    assert_eq!(usage[2]["calls"], 1);
+    assert_eq!(usage[2]["avg_duration_ms"], 5.0);
+}
+
+/// Test that the leaderboard is hidden unless enabled
+#[actix_web::test]
+async fn test_tool_usage_disabled_by_default() {
+    let resp = fetch_usage(Config::default(), AppState::new()).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,28 @@
+use actix_web::{test, web, App};
+use rust_mcp_server::dashboard::handlers::get_version;
+use rust_mcp_server::shared::config::Config;
+
+/// Test that the version endpoint reports the package version and build metadata
+#[actix_web::test]
+async fn test_version_endpoint() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(Config::default()))
+            .route("/api/version", web::get().to(get_version)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/version").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(body["features"].is_array());
+    assert!(body["git_hash"]
+        .as_str()
+        .is_some_and(|hash| !hash.is_empty()));
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(body["build_timestamp"].as_str().unwrap()).is_ok()
+    );
+}
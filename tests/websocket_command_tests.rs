@@ -0,0 +1,73 @@
+use actix_web::{web, App};
+use awc::ws;
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use rust_mcp_server::dashboard::websocket::{websocket_handler, WebSocketRateLimiter};
+use rust_mcp_server::shared::{config::Config, reload, state::AppState};
+
+fn start_server() -> actix_test::TestServer {
+    let mut config = Config::default();
+    // The test client sends no Origin header
+    config.development.enable_cors = true;
+    let state = AppState::new();
+    let config = reload::shared(config);
+
+    actix_test::start(move || {
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .app_data(web::Data::new(config.clone()))
+            .app_data(web::Data::new(WebSocketRateLimiter::default()))
+            .route("/ws", web::get().to(websocket_handler))
+    })
+}
+
+async fn reply<F>(framed: &mut F, command: &str) -> serde_json::Value
+where
+    F: Sink<ws::Message, Error = awc::error::WsProtocolError>
+        + Stream<Item = Result<ws::Frame, awc::error::WsProtocolError>>
+        + Unpin,
+{
+    framed
+        .send(ws::Message::Text(command.to_string().into()))
+        .await
+        .unwrap();
+    match framed.next().await {
+        Some(Ok(ws::Frame::Text(bytes))) => serde_json::from_slice(&bytes).unwrap(),
+        other => panic!("expected a text frame, got {:?}", other),
+    }
+}
+
+/// Test that a ping command is answered with a pong
+#[actix_web::test]
+async fn test_ping_replies_pong() {
+    let mut srv = start_server();
+    let mut framed = srv.ws_at("/ws").await.unwrap();
+
+    let pong = reply(&mut framed, r#"{"cmd":"ping"}"#).await;
+    assert_eq!(pong, serde_json::json!({ "type": "pong" }));
+}
+
+/// Test that bad commands get error frames without closing the connection
+#[actix_web::test]
+async fn test_invalid_commands_keep_connection_open() {
+    let mut srv = start_server();
+    let mut framed = srv.ws_at("/ws").await.unwrap();
+
+    let error = reply(&mut framed, "not json").await;
+    assert_eq!(error["type"], "command_error");
+
+    let error = reply(&mut framed, r#"{"cmd":"launch"}"#).await;
+    assert_eq!(error["type"], "command_error");
+
+    let subscribed = reply(
+        &mut framed,
+        r#"{"cmd":"subscribe","types":["error","not_a_type"]}"#,
+    )
+    .await;
+    assert_eq!(
+        subscribed,
+        serde_json::json!({ "type": "subscribed", "types": ["error"] })
+    );
+
+    let pong = reply(&mut framed, r#"{"cmd":"ping"}"#).await;
+    assert_eq!(pong["type"], "pong");
+}
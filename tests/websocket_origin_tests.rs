@@ -1,7 +1,7 @@
 use actix_web::test;
 use actix_web::{web, App};
-use rust_mcp_server::dashboard::websocket::websocket_handler;
-use rust_mcp_server::shared::{config::Config, state::AppState};
+use rust_mcp_server::dashboard::websocket::{websocket_handler, WebSocketRateLimiter};
+use rust_mcp_server::shared::{config::Config, reload, state::AppState};
 
 /// Test WebSocket origin validation in production mode
 #[actix_web::test]
@@ -17,7 +17,8 @@ async fn test_websocket_origin_validation_production() {
     let app = test::init_service(
         App::new()
             .app_data(web::Data::new(state))
-            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(WebSocketRateLimiter::default()))
             .route("/ws", web::get().to(websocket_handler)),
     )
     .await;
@@ -60,7 +61,8 @@ async fn test_websocket_origin_validation_development() {
     let app = test::init_service(
         App::new()
             .app_data(web::Data::new(state))
-            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(WebSocketRateLimiter::default()))
             .route("/ws", web::get().to(websocket_handler)),
     )
     .await;
@@ -90,7 +92,8 @@ async fn test_websocket_no_origin_header() {
     let app = test::init_service(
         App::new()
             .app_data(web::Data::new(state))
-            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(WebSocketRateLimiter::default()))
             .route("/ws", web::get().to(websocket_handler)),
     )
     .await;
@@ -123,7 +126,8 @@ async fn test_websocket_multiple_allowed_origins() {
     let app = test::init_service(
         App::new()
             .app_data(web::Data::new(state))
-            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(WebSocketRateLimiter::default()))
             .route("/ws", web::get().to(websocket_handler)),
     )
     .await;
@@ -201,7 +205,8 @@ async fn test_websocket_origin_case_sensitivity() {
     let app = test::init_service(
         App::new()
             .app_data(web::Data::new(state))
-            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(reload::shared(config)))
+            .app_data(web::Data::new(WebSocketRateLimiter::default()))
             .route("/ws", web::get().to(websocket_handler)),
     )
     .await;